@@ -233,6 +233,11 @@ pub fn handle_new_command(
             get_logs_settings: None,
             reth: final_reth_config,
             multicall3_address: None,
+            reorg_handling: None,
+            confirmation_blocks: None,
+            finality: None,
+            min_reorg_depth_to_report: None,
+            reorg_polling: None,
         }],
         contracts: vec![Contract {
             name: "RocketPoolETH".to_string(),
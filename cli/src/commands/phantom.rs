@@ -549,6 +549,11 @@ async fn handle_phantom_deploy(
                         get_logs_settings: None,
                         reth: None,
                         multicall3_address: None,
+                        reorg_handling: None,
+                        confirmation_blocks: None,
+                        finality: None,
+                        min_reorg_depth_to_report: None,
+                        reorg_polling: None,
                     });
                 }
 
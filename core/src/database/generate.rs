@@ -19,6 +19,7 @@ fn generate_event_table_sql_with_comments(
     contract_name: &str,
     schema_name: &str,
     apply_full_name_comment_for_events: Vec<String>,
+    block_number_index: bool,
 ) -> String {
     abi_inputs
         .iter()
@@ -46,6 +47,20 @@ fn generate_event_table_sql_with_comments(
                 );"
             );
 
+            // BRIN suits the append-only block ordering of event tables, so block range reads
+            // avoid a full table scan without slowing down bulk inserts
+            let create_table_sql = if block_number_index {
+                let index_name = compact_table_name_if_needed(format!(
+                    "{}_block_number_brin",
+                    camel_to_snake(&event_info.name)
+                ));
+                format!(
+                    "{create_table_sql}\nCREATE INDEX IF NOT EXISTS {index_name} ON {table_name} USING BRIN (block_number);"
+                )
+            } else {
+                create_table_sql
+            };
+
             if !apply_full_name_comment_for_events.contains(&event_info.name) {
                 return create_table_sql;
             }
@@ -351,6 +366,7 @@ pub fn generate_tables_for_indexer_sql(
     project_path: &Path,
     indexer: &Indexer,
     disable_event_tables: bool,
+    block_number_index: bool,
 ) -> Result<Code, GenerateTablesForIndexerSqlError> {
     let mut sql = "CREATE SCHEMA IF NOT EXISTS rindexer_internal;".to_string();
 
@@ -386,6 +402,7 @@ pub fn generate_tables_for_indexer_sql(
                     &contract.name,
                     &schema_name,
                     event_matching_name_on_other,
+                    block_number_index,
                 ));
             }
 
@@ -440,6 +457,7 @@ pub fn generate_tables_for_indexer_sql(
                 &contract_name,
                 &schema_name,
                 event_matching_name_on_other,
+                block_number_index,
             ));
         }
         sql.push_str(&generate_internal_event_table_sql(&event_names, &schema_name, networks));
@@ -611,3 +629,69 @@ pub fn drop_tables_for_indexer_sql(project_path: &Path, indexer: &Indexer) -> Co
 
     Code::new(sql)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer_event() -> EventInfo {
+        serde_json::from_value(serde_json::json!({
+            "name": "Transfer",
+            "inputs": [{ "indexed": true, "name": "from", "type": "address" }],
+            "signature": "Transfer(address)",
+            "struct_result": "TransferResult",
+            "struct_data": "TransferData",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_event_table_sql_block_number_index() {
+        let sql = generate_event_table_sql_with_comments(
+            &[transfer_event()],
+            "ERC20",
+            "indexer_erc20",
+            vec![],
+            true,
+        );
+
+        assert!(sql.contains(
+            "CREATE INDEX IF NOT EXISTS transfer_block_number_brin ON indexer_erc20.transfer USING BRIN (block_number);"
+        ));
+    }
+
+    #[test]
+    fn test_event_table_sql_without_block_number_index() {
+        let sql = generate_event_table_sql_with_comments(
+            &[transfer_event()],
+            "ERC20",
+            "indexer_erc20",
+            vec![],
+            false,
+        );
+
+        assert!(sql.contains("CREATE TABLE IF NOT EXISTS indexer_erc20.transfer"));
+        assert!(!sql.contains("CREATE INDEX"));
+    }
+
+    #[test]
+    fn test_event_table_sql_block_number_index_long_event_name() {
+        let mut event = transfer_event();
+        event.name = "TransferWithAVeryLongEventNameThatGoesOnAndOnForever".to_string();
+
+        let sql = generate_event_table_sql_with_comments(
+            &[event.clone()],
+            "ERC20",
+            "indexer_erc20",
+            vec![],
+            true,
+        );
+
+        let index_name = compact_table_name_if_needed(format!(
+            "{}_block_number_brin",
+            camel_to_snake(&event.name)
+        ));
+        assert!(index_name.len() <= 63);
+        assert!(sql.contains(&format!("CREATE INDEX IF NOT EXISTS {index_name} ON")));
+    }
+}
@@ -48,10 +48,13 @@ pub async fn setup_postgres(
         info!("Creating tables for {}", manifest.name);
     }
 
+    // reorgs delete by block number so make sure those deletes can use an index
+    let block_number_index = manifest.networks.iter().any(|n| n.has_reorg_notifications());
     let sql = generate_tables_for_indexer_sql(
         project_path,
         &manifest.to_indexer(),
         disable_event_tables,
+        block_number_index,
     )?;
 
     debug!("{}", sql);
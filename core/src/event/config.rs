@@ -20,7 +20,9 @@ use crate::{
         BuildRindexerFilterError, RindexerEventFilter,
     },
     indexer::IndexingEventsProgressState,
-    manifest::{native_transfer::TraceProcessingMethod, storage::CsvDetails},
+    manifest::{
+        native_transfer::TraceProcessingMethod, network::ReorgPolling, storage::CsvDetails,
+    },
     PostgresClient,
 };
 
@@ -45,6 +47,10 @@ pub struct ContractEventProcessingConfig {
     pub index_event_in_order: bool,
     pub live_indexing: bool,
     pub indexing_distance_from_head: U64,
+    /// How far the RPC can go back on the latest block before it is treated as out of sync.
+    pub chain_reorg_safe_distance: U64,
+    /// Reorg polling for networks without chain state notifications.
+    pub reorg_polling: Option<ReorgPolling>,
     /// Per-generation cancellation token for hot-reload support.
     pub cancel_token: CancellationToken,
 }
@@ -123,6 +129,10 @@ pub struct FactoryEventProcessingConfig {
     pub index_event_in_order: bool,
     pub live_indexing: bool,
     pub indexing_distance_from_head: U64,
+    /// How far the RPC can go back on the latest block before it is treated as out of sync.
+    pub chain_reorg_safe_distance: U64,
+    /// Reorg polling for networks without chain state notifications.
+    pub reorg_polling: Option<ReorgPolling>,
     /// Per-generation cancellation token for hot-reload support.
     pub cancel_token: CancellationToken,
 }
@@ -280,6 +290,20 @@ impl EventProcessingConfig {
         }
     }
 
+    pub fn chain_reorg_safe_distance(&self) -> U64 {
+        match self {
+            Self::ContractEventProcessing(config) => config.chain_reorg_safe_distance,
+            Self::FactoryEventProcessing(config) => config.chain_reorg_safe_distance,
+        }
+    }
+
+    pub fn reorg_polling(&self) -> Option<ReorgPolling> {
+        match self {
+            Self::ContractEventProcessing(config) => config.reorg_polling,
+            Self::FactoryEventProcessing(config) => config.reorg_polling,
+        }
+    }
+
     pub fn progress(&self) -> Arc<Mutex<IndexingEventsProgressState>> {
         match self {
             Self::ContractEventProcessing(config) => config.progress.clone(),
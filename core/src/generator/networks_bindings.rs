@@ -25,15 +25,28 @@ fn generate_reth_init_fn(_network: &Network) -> Code {
 #[cfg(feature = "reth")]
 fn generate_reth_init_fn(network: &Network) -> Code {
     if network.is_reth_enabled() {
-        let reth_cli_args = network.reth.as_ref().unwrap().to_cli_args();
+        let reth = network.reth.as_ref().unwrap();
+        let reth_cli_args = reth.to_cli_args();
+        let notification_channel_capacity = reth.notification_channel_capacity();
+        let reverted_grace_period_ms = reth.reverted_grace_period().as_millis();
         Code::new(format!(
             r#"
-            use rindexer::reth::node::start_reth_node_with_exex;
+            use rindexer::reth::node::{{start_reth_node_with_exex, RethNodeOptions}};
             use rindexer::reth::Cli;
             let cli = Cli::try_parse_args_from({reth_cli_args:?}).unwrap();
-            let chain_state_notification = start_reth_node_with_exex(cli).unwrap();
-            let chain_state_notification = Some(chain_state_notification);
-            "#
+            let options = RethNodeOptions {{
+                notification_channel_capacity: {notification_channel_capacity},
+                reverted_grace_period: std::time::Duration::from_millis({reverted_grace_period_ms}),
+            }};
+            let chain_state_notification = start_reth_node_with_exex(cli, options).unwrap();
+            {keep_chain_state_notification}
+            "#,
+            keep_chain_state_notification = if network.is_reorg_handling_enabled() {
+                "let chain_state_notification = Some(chain_state_notification);"
+            } else {
+                // the node is still needed for its IPC provider but reorg handling is disabled
+                "let _ = chain_state_notification; let chain_state_notification = None;"
+            },
         ))
     } else {
         Code::new(
@@ -204,3 +217,32 @@ pub fn generate_networks_code(networks: &[Network]) -> Code {
 
     output
 }
+
+#[cfg(all(test, feature = "reth"))]
+mod tests {
+    use super::*;
+    use crate::manifest::network::test_network;
+
+    fn reth_network(reorg_handling: bool) -> Network {
+        test_network(&format!(
+            "reorg_handling: {reorg_handling}\nreth:\n  enabled: true\n  cli_args:\n    - --chain sepolia"
+        ))
+    }
+
+    #[test]
+    fn test_reth_init_binds_no_notification_when_reorg_handling_disabled() {
+        let code = generate_reth_init_fn(&reth_network(false));
+
+        assert!(code.as_str().contains("let chain_state_notification = None;"));
+        assert!(!code.as_str().contains("Some(chain_state_notification)"));
+    }
+
+    #[test]
+    fn test_reth_init_binds_notification_when_reorg_handling_enabled() {
+        let code = generate_reth_init_fn(&reth_network(true));
+
+        assert!(code
+            .as_str()
+            .contains("let chain_state_notification = Some(chain_state_notification);"));
+    }
+}
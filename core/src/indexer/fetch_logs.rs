@@ -1,10 +1,11 @@
 use crate::blockclock::BlockClock;
 use crate::helpers::{halved_block_number, is_relevant_block};
-use crate::indexer::reorg::reorg_safe_distance_for_chain;
+use crate::indexer::reorg::{safe_block_number, spawn_chain_notification_listener};
 use crate::{
     event::{config::EventProcessingConfig, RindexerEventFilter},
-    indexer::{reorg::handle_chain_notification, IndexingEventProgressStatus},
+    indexer::IndexingEventProgressStatus,
     is_running,
+    manifest::network::ReorgPolling,
     provider::{JsonRpcCachedProvider, ProviderError},
 };
 use alloy::{
@@ -134,6 +135,8 @@ pub fn fetch_logs_stream(
                 snapshot_to_block,
                 &config.topic_id(),
                 &config.indexing_distance_from_head(),
+                config.chain_reorg_safe_distance(),
+                config.reorg_polling(),
                 current_filter,
                 &config.info_log_name(),
                 &config.network_contract().network,
@@ -396,6 +399,8 @@ async fn live_indexing_stream(
     last_seen_block_number: U64,
     topic_id: &B256,
     reorg_safe_distance: &U64,
+    chain_reorg_safe_distance: U64,
+    reorg_polling: Option<ReorgPolling>,
     mut current_filter: RindexerEventFilter,
     info_log_name: &str,
     network: &str,
@@ -409,19 +414,13 @@ async fn live_indexing_stream(
     let log_no_new_block_interval = Duration::from_secs(300);
     let target_iteration_duration = Duration::from_millis(200);
 
-    let chain_state_notification = cached_provider.get_chain_state_notification();
-
     // Spawn a separate task to handle notifications
-    if let Some(notifications) = chain_state_notification {
-        let info_log_name = info_log_name.to_string();
-        let network = network.to_string();
-        tokio::spawn(async move {
-            let mut notifications_clone = notifications.subscribe();
-            while let Ok(notification) = notifications_clone.recv().await {
-                handle_chain_notification(notification, &info_log_name, &network);
-            }
-        });
-    }
+    spawn_chain_notification_listener(
+        cached_provider,
+        reorg_polling,
+        info_log_name.to_string(),
+        network.to_string(),
+    );
 
     // This is a local cache of the last blocks we've crawled and their timestamps.
     //
@@ -471,13 +470,14 @@ async fn live_indexing_stream(
                             last_seen_block_number
                         );
 
-                        let safe_block_number = latest_block_number - reorg_safe_distance;
+                        let safe_block_number =
+                            safe_block_number(latest_block_number, *reorg_safe_distance);
                         let from_block = current_filter.from_block();
                         if from_block > safe_block_number {
                             if reorg_safe_distance.is_zero() {
                                 let block_distance = from_block - latest_block_number;
-                                let is_outside_reorg_range = block_distance
-                                    > reorg_safe_distance_for_chain(cached_provider.chain.id());
+                                let is_outside_reorg_range =
+                                    block_distance > chain_reorg_safe_distance;
 
                                 // it should never get under normal conditions outside the reorg range,
                                 // therefore, we log an error as means RCP state is not in sync with the blockchain
@@ -23,7 +23,8 @@ use crate::{
     },
     event::config::{EventProcessingConfig, TraceProcessingConfig},
     helpers::get_full_path,
-    manifest::{storage::CsvDetails, stream::StreamsConfig},
+    indexer::native_transfer::EVENT_NAME as NATIVE_TRANSFER_EVENT_NAME,
+    manifest::{network::Network, storage::CsvDetails, stream::StreamsConfig},
     metrics::indexing as metrics,
     EthereumSqlTypeWrapper, PostgresClient,
 };
@@ -219,6 +220,25 @@ pub async fn get_last_synced_block_number(config: SyncConfig<'_>) -> Option<U64>
     None
 }
 
+/// The highest block of an event which is safe to read, as it can no longer be reorged.
+///
+/// This is the last synced block for `config.event_name` on `config.network` minus the network's
+/// reorg safe distance. Returns zero if the event has not synced anything past that distance yet.
+pub async fn safe_checkpoint(config: SyncConfig<'_>, network: &Network) -> U64 {
+    let last_synced_block = get_last_synced_block_number(config).await;
+
+    safe_block_from_checkpoint(last_synced_block, network.reorg_safe_distance())
+}
+
+/// The internal checkpoint table shared by all trace events, created for the native transfer event.
+fn trace_internal_event_table_name(schema: &str) -> String {
+    generate_internal_event_table_name(schema, NATIVE_TRANSFER_EVENT_NAME)
+}
+
+fn safe_block_from_checkpoint(last_synced_block: Option<U64>, reorg_safe_distance: U64) -> U64 {
+    last_synced_block.unwrap_or_default().saturating_sub(reorg_safe_distance)
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum UpdateLastSyncedBlockNumberFile {
     #[error("File IO error: {0}")]
@@ -404,7 +424,7 @@ pub async fn evm_trace_update_progress_and_last_synced_task(
         // Use the native_transfer table for all trace events since they share the same pipeline
         let schema =
             generate_indexer_contract_schema_name(&config.indexer_name, &config.contract_name);
-        let table_name = generate_internal_event_table_name(&schema, "native_transfer");
+        let table_name = trace_internal_event_table_name(&schema);
         let query = format!(
                 "UPDATE rindexer_internal.{table_name} SET last_synced_block = $1 WHERE network = $2 AND $1 > last_synced_block"
             );
@@ -599,3 +619,89 @@ pub async fn update_last_synced_cron_block(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::network::test_network;
+
+    #[test]
+    fn test_trace_internal_event_table_name() {
+        assert_eq!(
+            trace_internal_event_table_name("rindexer_evm_traces"),
+            "rindexer_evm_traces_native_transfer"
+        );
+    }
+
+    #[test]
+    fn test_safe_block_from_checkpoint() {
+        assert_eq!(
+            safe_block_from_checkpoint(Some(U64::from(1000)), U64::from(12)),
+            U64::from(988)
+        );
+        assert_eq!(safe_block_from_checkpoint(Some(U64::from(5)), U64::from(12)), U64::ZERO);
+        assert_eq!(safe_block_from_checkpoint(None, U64::from(12)), U64::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_safe_checkpoint_from_csv_checkpoint() {
+        let project = tempfile::tempdir().unwrap();
+        let csv_path = project.path().join("generated_csv");
+        let checkpoint_path = build_last_synced_block_number_file(
+            &csv_path,
+            "ERC20Transfers",
+            "ethereum",
+            "Transfer",
+        );
+        fs::create_dir_all(Path::new(&checkpoint_path).parent().unwrap()).await.unwrap();
+        fs::write(&checkpoint_path, "1000").await.unwrap();
+
+        let csv_details = Some(CsvDetails {
+            enabled: true,
+            path: csv_path.to_string_lossy().into_owned(),
+            disable_create_headers: None,
+        });
+        let sync_config = || SyncConfig {
+            project_path: project.path(),
+            postgres: &None,
+            clickhouse: &None,
+            csv_details: &csv_details,
+            stream_details: &None,
+            contract_csv_enabled: true,
+            indexer_name: "Indexer",
+            contract_name: "ERC20Transfers",
+            event_name: "Transfer",
+            network: "ethereum",
+        };
+
+        assert_eq!(safe_checkpoint(sync_config(), &test_network("")).await, U64::from(988));
+        assert_eq!(
+            safe_checkpoint(sync_config(), &test_network("finality: instant")).await,
+            U64::from(1000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_safe_checkpoint_without_checkpoint() {
+        let project = tempfile::tempdir().unwrap();
+        let csv_details = Some(CsvDetails {
+            enabled: true,
+            path: project.path().to_string_lossy().into_owned(),
+            disable_create_headers: None,
+        });
+        let sync_config = SyncConfig {
+            project_path: project.path(),
+            postgres: &None,
+            clickhouse: &None,
+            csv_details: &csv_details,
+            stream_details: &None,
+            contract_csv_enabled: true,
+            indexer_name: "Indexer",
+            contract_name: "ERC20Transfers",
+            event_name: "Transfer",
+            network: "ethereum",
+        };
+
+        assert_eq!(safe_checkpoint(sync_config, &test_network("")).await, U64::ZERO);
+    }
+}
@@ -11,9 +11,15 @@ pub use dependency::ContractEventDependenciesMapFromRelationshipsError;
 mod fetch_logs;
 pub use fetch_logs::FetchLogsResult;
 mod last_synced;
+pub use last_synced::{safe_checkpoint, SyncConfig};
 pub mod native_transfer;
 pub mod no_code;
 mod reorg;
+pub use reorg::{
+    compute_orphaned_txs, drive_chain_notifications, BroadcastNotificationSource,
+    PollingNotificationSource, ReorgNotificationSource, DEFAULT_REORG_SAFE_BLOCKS,
+    MAINNET_REORG_SAFE_BLOCKS,
+};
 pub mod start;
 pub mod task_tracker;
 
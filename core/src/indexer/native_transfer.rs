@@ -23,10 +23,10 @@ use crate::{
     indexer::{
         last_synced::evm_trace_update_progress_and_last_synced_task,
         process::ProcessEventError,
-        reorg::handle_chain_notification,
+        reorg::{safe_block_number, spawn_chain_notification_listener},
         task_tracker::{indexing_event_processed, indexing_event_processing},
     },
-    manifest::native_transfer::TraceProcessingMethod,
+    manifest::{native_transfer::TraceProcessingMethod, network::ReorgPolling},
     provider::{JsonRpcCachedProvider, ProviderError},
 };
 
@@ -106,30 +106,26 @@ async fn push_range(block_tx: &mpsc::Sender<U64>, last: U64, latest: U64) {
 ///
 /// This process respects channel backpressure and will only complete once the `end_block` is
 /// reached.
+#[allow(clippy::too_many_arguments)]
 pub async fn native_transfer_block_fetch(
     publisher: Arc<JsonRpcCachedProvider>,
     block_tx: mpsc::Sender<U64>,
     start_block: U64,
     end_block: Option<U64>,
     indexing_distance_from_head: U64,
+    reorg_polling: Option<ReorgPolling>,
     network: String,
     cancel_token: CancellationToken,
 ) -> Result<(), ProcessEventError> {
     let mut last_seen_block = start_block;
 
-    let chain_state_notification = publisher.get_chain_state_notification();
-
     // Spawn a separate task to handle notifications
-    if let Some(notifications) = chain_state_notification {
-        // Subscribe to notifications for this network
-        let mut notifications_clone = notifications.subscribe();
-        let network_clone = network.clone();
-        tokio::spawn(async move {
-            while let Ok(notification) = notifications_clone.recv().await {
-                handle_chain_notification(notification, "NativeTransfer", &network_clone);
-            }
-        });
-    }
+    spawn_chain_notification_listener(
+        &publisher,
+        reorg_polling,
+        EVENT_NAME.to_string(),
+        network.clone(),
+    );
 
     loop {
         if !is_running() || cancel_token.is_cancelled() {
@@ -144,7 +140,7 @@ pub async fn native_transfer_block_fetch(
                 let block = U64::from(latest_block.header.number);
 
                 // Always trim back to the safe indexing threshold (which is zero if disabled)
-                let block = block - indexing_distance_from_head;
+                let block = safe_block_number(block, indexing_distance_from_head);
 
                 if block > last_seen_block {
                     let to_block = end_block.map(|end| block.min(end)).unwrap_or(block);
@@ -12,7 +12,7 @@ use tokio::{
 use tracing::{debug, error, info};
 
 use crate::helpers::is_relevant_block;
-use crate::indexer::reorg::reorg_safe_distance_for_chain;
+use crate::indexer::reorg::safe_block_number;
 use crate::metrics::indexing as metrics;
 use crate::provider::JsonRpcCachedProvider;
 use crate::{
@@ -404,7 +404,7 @@ async fn live_indexing_for_contract_event_dependencies(
                 ordering_live_indexing_details.last_seen_block_number
             );
             let reorg_safe_distance = &config.indexing_distance_from_head();
-            let safe_block_number = latest_block_number - reorg_safe_distance;
+            let safe_block_number = safe_block_number(latest_block_number, *reorg_safe_distance);
             let from_block = ordering_live_indexing_details.filter.from_block();
 
             // check reorg distance and skip if not safe
@@ -412,7 +412,7 @@ async fn live_indexing_for_contract_event_dependencies(
                 if reorg_safe_distance.is_zero() {
                     let block_distance = from_block - latest_block_number;
                     let is_outside_reorg_range =
-                        block_distance > reorg_safe_distance_for_chain(cached_provider.chain.id());
+                        block_distance > config.chain_reorg_safe_distance();
 
                     // it should never get under normal conditions outside the reorg range,
                     // therefore, we log an error as means RCP state is not in sync with the blockchain
@@ -1,15 +1,54 @@
-use alloy::primitives::U64;
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+use alloy::primitives::{TxHash, B256, U64};
+use async_trait::async_trait;
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::JoinHandle,
+};
 use tracing::{debug, warn};
 
+use crate::manifest::network::ReorgPolling;
 use crate::metrics::indexing as metrics;
 use crate::notifications::ChainStateNotification;
+use crate::provider::{JsonRpcCachedProvider, ProviderError};
+
+/// How many blocks are walked back from each tip looking for their common ancestor.
+const MAX_ORPHANED_TXS_WALK_DEPTH: usize = 1024;
+
+/// Tracing target for reorg logs, so they can be filtered with `RUST_LOG=rindexer::reorg=debug`.
+const REORG_LOG_TARGET: &str = "rindexer::reorg";
+
+/// Details of a reorg or revert taken from a chain state notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReorgInfo {
+    /// The first block invalidated by the reorg.
+    pub fork_block: u64,
+    /// The depth of the reorg in blocks.
+    pub depth: u64,
+}
+
+impl ReorgInfo {
+    /// Reorg details for the inclusive reverted block range `from_block..=to_block`.
+    ///
+    /// Notifications send the range start first, a reorg of a single block has `from == to`.
+    fn from_reverted_range(from_block: u64, to_block: u64) -> Self {
+        Self { fork_block: from_block.min(to_block), depth: from_block.abs_diff(to_block) + 1 }
+    }
+}
 
 /// Handles chain state notifications (reorgs, reverts, commits)
+///
+/// Returns the reorg details for `Reorged` and `Reverted` notifications, `None` for commits.
 pub fn handle_chain_notification(
     notification: ChainStateNotification,
     info_log_name: &str,
     network: &str,
-) {
+) -> Option<ReorgInfo> {
     match notification {
         ChainStateNotification::Reorged {
             revert_from_block,
@@ -18,10 +57,11 @@ pub fn handle_chain_notification(
             new_to_block,
             new_tip_hash,
         } => {
-            let depth = revert_from_block.saturating_sub(revert_to_block);
-            metrics::record_reorg(network, depth);
+            let reorg = ReorgInfo::from_reverted_range(revert_from_block, revert_to_block);
+            metrics::record_reorg(network, reorg.depth, reorg.fork_block);
 
             warn!(
+                target: REORG_LOG_TARGET,
                 "{} - REORG DETECTED! Need to revert blocks {} to {} and re-index {} to {} (new tip: {})",
                 info_log_name,
                 revert_from_block, revert_to_block,
@@ -29,38 +69,597 @@ pub fn handle_chain_notification(
                 new_tip_hash
             );
             // TODO: In future PR, actually handle the reorg by reverting and re-indexing
+            Some(reorg)
         }
         ChainStateNotification::Reverted { from_block, to_block } => {
-            let depth = from_block.saturating_sub(to_block);
-            metrics::record_reorg(network, depth);
+            let reorg = ReorgInfo::from_reverted_range(from_block, to_block);
+            metrics::record_reorg(network, reorg.depth, reorg.fork_block);
 
             warn!(
+                target: REORG_LOG_TARGET,
                 "{} - CHAIN REVERTED! Blocks {} to {} have been reverted",
                 info_log_name, from_block, to_block
             );
             // TODO: In future PR, mark affected logs as removed in the database
+            Some(reorg)
         }
         ChainStateNotification::Committed { from_block, to_block, tip_hash } => {
             debug!(
+                target: REORG_LOG_TARGET,
                 "{} - Chain committed: blocks {} to {} (tip: {})",
                 info_log_name, from_block, to_block, tip_hash
             );
+            None
+        }
+    }
+}
+
+/// A source of chain state notifications, so reorgs can be fed by more than the reth ExEx.
+#[async_trait]
+pub trait ReorgNotificationSource: Send {
+    /// Waits for the next notification, returns `None` once the source is exhausted.
+    async fn next(&mut self) -> Option<ChainStateNotification>;
+}
+
+/// Notifications sent over the reth ExEx broadcast channel.
+///
+/// The notification channel is bounded and never blocks the sender, so a listener that falls
+/// behind loses the oldest notifications for good. When that happens the backlog still queued is
+/// drained and coalesced instead of the listener exiting. A deeper fork in the dropped
+/// notifications is not recovered.
+pub struct BroadcastNotificationSource {
+    receiver: broadcast::Receiver<ChainStateNotification>,
+    pending: VecDeque<ChainStateNotification>,
+    info_log_name: String,
+}
+
+impl BroadcastNotificationSource {
+    pub fn new(
+        receiver: broadcast::Receiver<ChainStateNotification>,
+        info_log_name: String,
+    ) -> Self {
+        Self { receiver, pending: VecDeque::new(), info_log_name }
+    }
+}
+
+#[async_trait]
+impl ReorgNotificationSource for BroadcastNotificationSource {
+    async fn next(&mut self) -> Option<ChainStateNotification> {
+        loop {
+            if let Some(notification) = self.pending.pop_front() {
+                return Some(notification);
+            }
+
+            match self.receiver.recv().await {
+                Ok(notification) => return Some(notification),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        target: REORG_LOG_TARGET,
+                        "{} - Chain state notifications lagged behind, {} notifications were dropped - coalescing backlog",
+                        self.info_log_name, skipped
+                    );
+                    self.pending.extend(drain_coalesced(&mut self.receiver));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Detects reorgs by polling the latest block and checking it builds on the blocks seen before.
+///
+/// For providers without chain state notifications. Only the last `depth` block hashes are kept,
+/// so a reorg deeper than that is reported from the oldest block still tracked.
+pub struct PollingNotificationSource {
+    provider: Arc<JsonRpcCachedProvider>,
+    poll_interval: Duration,
+    depth: usize,
+    /// Recently seen canonical blocks, oldest first.
+    recent: VecDeque<(u64, B256)>,
+    info_log_name: String,
+}
+
+impl PollingNotificationSource {
+    pub fn new(
+        provider: Arc<JsonRpcCachedProvider>,
+        poll_interval: Duration,
+        depth: usize,
+        info_log_name: String,
+    ) -> Self {
+        Self {
+            provider,
+            poll_interval,
+            depth: depth.max(1),
+            recent: VecDeque::new(),
+            info_log_name,
+        }
+    }
+
+    fn track(&mut self, number: u64, hash: B256) {
+        while self.recent.back().is_some_and(|(seen, _)| *seen >= number) {
+            self.recent.pop_back();
+        }
+        self.recent.push_back((number, hash));
+        while self.recent.len() > self.depth {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Walks back from the new head until it meets a tracked block, returning the new blocks
+    /// from oldest to newest and the common ancestor if one was found.
+    async fn find_new_branch(
+        &self,
+        head_number: u64,
+        head_hash: B256,
+        parent_hash: B256,
+    ) -> Result<(Vec<(u64, B256)>, Option<u64>), ProviderError> {
+        let mut branch = vec![(head_number, head_hash)];
+        let mut parent = parent_hash;
+
+        for _ in 0..self.depth {
+            if let Some((number, _)) = self.recent.iter().find(|(_, hash)| *hash == parent) {
+                branch.reverse();
+                return Ok((branch, Some(*number)));
+            }
+
+            let Some(block) = self.provider.get_block_by_hash(parent).await? else {
+                break;
+            };
+            branch.push((block.header.number, block.header.hash));
+            parent = block.header.parent_hash;
+        }
+
+        branch.reverse();
+        Ok((branch, None))
+    }
+
+    async fn poll(&mut self) -> Result<Option<ChainStateNotification>, ProviderError> {
+        let Some(head) = self.provider.get_latest_block().await? else {
+            return Ok(None);
+        };
+        let (head_number, head_hash) = (head.header.number, head.header.hash);
+
+        let Some(&(last_number, last_hash)) = self.recent.back() else {
+            self.track(head_number, head_hash);
+            return Ok(None);
+        };
+
+        // nothing new, or the RPC is behind on a block already seen
+        if self.recent.iter().any(|(_, hash)| *hash == head_hash) {
+            return Ok(None);
+        }
+
+        if head.header.parent_hash == last_hash {
+            self.track(head_number, head_hash);
+            return Ok(Some(ChainStateNotification::Committed {
+                from_block: head_number,
+                to_block: head_number,
+                tip_hash: head_hash,
+            }));
+        }
+
+        let (branch, ancestor) =
+            self.find_new_branch(head_number, head_hash, head.header.parent_hash).await?;
+
+        // several blocks arrived since the last poll, all building on the last tracked block
+        if ancestor == Some(last_number) {
+            for (number, hash) in branch {
+                self.track(number, hash);
+            }
+            return Ok(Some(ChainStateNotification::Committed {
+                from_block: last_number + 1,
+                to_block: head_number,
+                tip_hash: head_hash,
+            }));
+        }
+
+        let fork_block = match ancestor {
+            Some(ancestor) => ancestor + 1,
+            None => {
+                warn!(
+                    target: REORG_LOG_TARGET,
+                    "{} - Reorg is deeper than the {} tracked blocks, reporting from the oldest tracked block",
+                    self.info_log_name, self.depth
+                );
+                self.recent.front().map_or(head_number, |(number, _)| *number)
+            }
+        };
+
+        if ancestor.is_none() {
+            self.recent.clear();
+        }
+        for (number, hash) in branch {
+            self.track(number, hash);
+        }
+
+        Ok(Some(ChainStateNotification::Reorged {
+            revert_from_block: fork_block,
+            revert_to_block: last_number,
+            new_from_block: fork_block,
+            new_to_block: head_number,
+            new_tip_hash: head_hash,
+        }))
+    }
+}
+
+#[async_trait]
+impl ReorgNotificationSource for PollingNotificationSource {
+    async fn next(&mut self) -> Option<ChainStateNotification> {
+        loop {
+            match self.poll().await {
+                Ok(Some(notification)) => return Some(notification),
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(
+                        target: REORG_LOG_TARGET,
+                        "{} - Failed to poll for reorgs: {}", self.info_log_name, e
+                    );
+                }
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+/// Drives a notification source, handling every notification until the source is exhausted.
+pub async fn drive_chain_notifications(
+    mut source: impl ReorgNotificationSource,
+    info_log_name: String,
+    network: String,
+) {
+    while let Some(notification) = source.next().await {
+        handle_chain_notification(notification, &info_log_name, &network);
+    }
+}
+
+/// Listens for chain state notifications from the reth ExEx until the channel is closed.
+pub async fn listen_for_chain_notifications(
+    notifications: broadcast::Receiver<ChainStateNotification>,
+    info_log_name: String,
+    network: String,
+) {
+    let source = BroadcastNotificationSource::new(notifications, info_log_name.clone());
+    drive_chain_notifications(source, info_log_name, network).await;
+}
+
+/// Spawns the reorg listener for a network.
+///
+/// Reth chain state notifications are used when the provider has them, otherwise reorgs are
+/// detected by polling the provider when `reorg_polling` is set. Returns `None` when neither is
+/// available.
+pub fn spawn_chain_notification_listener(
+    provider: &Arc<JsonRpcCachedProvider>,
+    reorg_polling: Option<ReorgPolling>,
+    info_log_name: String,
+    network: String,
+) -> Option<JoinHandle<()>> {
+    if let Some(notifications) = provider.get_chain_state_notification() {
+        return Some(tokio::spawn(listen_for_chain_notifications(
+            notifications.subscribe(),
+            info_log_name,
+            network,
+        )));
+    }
+
+    let polling = reorg_polling?;
+    let source = PollingNotificationSource::new(
+        Arc::clone(provider),
+        polling.poll_interval(),
+        polling.depth(),
+        info_log_name.clone(),
+    );
+    Some(tokio::spawn(drive_chain_notifications(source, info_log_name, network)))
+}
+
+/// Relays chain state notifications from `upstream` to `downstream`, holding back `Reverted`
+/// notifications for `reverted_grace_period`.
+///
+/// Reth can emit a `Reverted` that is quickly superseded by a deeper `Reorged`. If a `Reorged`
+/// arrives within the grace period the two are merged into a single reorg at the deepest fork,
+/// otherwise the `Reverted` is forwarded as-is once the grace period elapses. A zero grace period
+/// forwards every notification immediately. If the relay falls behind, the backlog still queued is
+/// coalesced the same way as in [`BroadcastNotificationSource`].
+pub async fn relay_chain_notifications(
+    mut upstream: broadcast::Receiver<ChainStateNotification>,
+    downstream: broadcast::Sender<ChainStateNotification>,
+    reverted_grace_period: Duration,
+) {
+    let mut relay = RevertRelay { downstream, reverted_grace_period, pending_revert: None };
+
+    loop {
+        let next = match &relay.pending_revert {
+            Some((_, deadline)) => {
+                match tokio::time::timeout_at(*deadline, upstream.recv()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        // nothing superseded the revert in time so it is trusted
+                        relay.flush();
+                        continue;
+                    }
+                }
+            }
+            None => upstream.recv().await,
+        };
+
+        match next {
+            Ok(notification) => relay.relay(notification),
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(
+                    target: REORG_LOG_TARGET,
+                    "Chain state notification relay lagged behind, {} notifications were dropped - coalescing backlog",
+                    skipped
+                );
+                for notification in drain_coalesced(&mut upstream) {
+                    relay.relay(notification);
+                }
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+
+    relay.flush();
+}
+
+/// State of [`relay_chain_notifications`], the held back revert and when it is trusted.
+struct RevertRelay {
+    downstream: broadcast::Sender<ChainStateNotification>,
+    reverted_grace_period: Duration,
+    pending_revert: Option<(ChainStateNotification, tokio::time::Instant)>,
+}
+
+impl RevertRelay {
+    fn relay(&mut self, notification: ChainStateNotification) {
+        match notification {
+            ChainStateNotification::Reverted { .. } if !self.reverted_grace_period.is_zero() => {
+                // a later revert is merged without extending the original grace period
+                self.pending_revert = Some(match self.pending_revert.take() {
+                    Some((previous, deadline)) => (merge_reorgs(previous, notification), deadline),
+                    None => {
+                        (notification, tokio::time::Instant::now() + self.reverted_grace_period)
+                    }
+                });
+            }
+            ChainStateNotification::Reorged { .. } => {
+                let notification = match self.pending_revert.take() {
+                    Some((reverted, _)) => {
+                        debug!(
+                            target: REORG_LOG_TARGET,
+                            "Reverted notification superseded by a reorg, coalescing"
+                        );
+                        merge_reorgs(reverted, notification)
+                    }
+                    None => notification,
+                };
+                let _ = self.downstream.send(notification);
+            }
+            _ => {
+                self.flush();
+                let _ = self.downstream.send(notification);
+            }
+        }
+    }
+
+    /// Forwards the held back revert, if any.
+    fn flush(&mut self) {
+        if let Some((reverted, _)) = self.pending_revert.take() {
+            let _ = self.downstream.send(reverted);
+        }
+    }
+}
+
+/// Drains every notification currently queued on the receiver and coalesces them.
+fn drain_coalesced(
+    notifications: &mut broadcast::Receiver<ChainStateNotification>,
+) -> Vec<ChainStateNotification> {
+    let mut backlog = Vec::new();
+    while let Ok(notification) = notifications.try_recv() {
+        backlog.push(notification);
+    }
+
+    coalesce_notifications(backlog)
+}
+
+/// Coalesces a backlog of notifications into at most one reorg and one commit.
+///
+/// All `Reorged` and `Reverted` notifications are merged into a single notification spanning the
+/// deepest reverted range. A `Committed` notification is only kept if it arrived after the last
+/// reorg, as anything earlier has been superseded.
+fn coalesce_notifications(
+    notifications: Vec<ChainStateNotification>,
+) -> Vec<ChainStateNotification> {
+    let mut reorg: Option<ChainStateNotification> = None;
+    let mut committed: Option<ChainStateNotification> = None;
+
+    for notification in notifications {
+        match notification {
+            ChainStateNotification::Committed { .. } => committed = Some(notification),
+            _ => {
+                committed = None;
+                reorg = Some(match reorg {
+                    Some(previous) => merge_reorgs(previous, notification),
+                    None => notification,
+                });
+            }
         }
     }
+
+    reorg.into_iter().chain(committed).collect()
 }
 
+/// Merges two reorg-type notifications, `next` being the most recently received.
+fn merge_reorgs(
+    previous: ChainStateNotification,
+    next: ChainStateNotification,
+) -> ChainStateNotification {
+    let reverted_range = |notification: &ChainStateNotification| match *notification {
+        ChainStateNotification::Reorged { revert_from_block, revert_to_block, .. } => {
+            (revert_from_block, revert_to_block)
+        }
+        ChainStateNotification::Reverted { from_block, to_block } => (from_block, to_block),
+        ChainStateNotification::Committed { from_block, to_block, .. } => (from_block, to_block),
+    };
+
+    let (previous_from, previous_to) = reverted_range(&previous);
+    let (next_from, next_to) = reverted_range(&next);
+    let from_block = previous_from.min(next_from);
+    let to_block = previous_to.max(next_to);
+
+    match (previous, next) {
+        (
+            ChainStateNotification::Reorged {
+                new_from_block: previous_new_from,
+                new_to_block: previous_new_to,
+                ..
+            },
+            ChainStateNotification::Reorged { new_from_block, new_to_block, new_tip_hash, .. },
+        ) => ChainStateNotification::Reorged {
+            revert_from_block: from_block,
+            revert_to_block: to_block,
+            new_from_block: previous_new_from.min(new_from_block),
+            new_to_block: previous_new_to.max(new_to_block),
+            new_tip_hash,
+        },
+        (ChainStateNotification::Reorged { new_from_block, new_to_block, new_tip_hash, .. }, _)
+        | (_, ChainStateNotification::Reorged { new_from_block, new_to_block, new_tip_hash, .. }) => {
+            ChainStateNotification::Reorged {
+                revert_from_block: from_block,
+                revert_to_block: to_block,
+                new_from_block,
+                new_to_block,
+                new_tip_hash,
+            }
+        }
+        _ => ChainStateNotification::Reverted { from_block, to_block },
+    }
+}
+
+/// Blocks after which ethereum mainnet blocks are considered safe from reorgs.
+pub const MAINNET_REORG_SAFE_BLOCKS: u64 = 12;
+
+/// Blocks after which blocks on any other chain are considered safe from reorgs.
+pub const DEFAULT_REORG_SAFE_BLOCKS: u64 = 64;
+
 pub fn reorg_safe_distance_for_chain(chain_id: u64) -> U64 {
     if chain_id == 1 {
-        U64::from(12)
+        U64::from(MAINNET_REORG_SAFE_BLOCKS)
     } else {
-        U64::from(64)
+        U64::from(DEFAULT_REORG_SAFE_BLOCKS)
     }
 }
 
+/// Computes the transactions which were included in the old fork but not in the new one.
+///
+/// Both chains are walked back from their tips, one block at a time from whichever is higher, until
+/// they meet at the common ancestor. Transactions re-included on the new fork are not orphaned.
+pub async fn compute_orphaned_txs(
+    provider: &JsonRpcCachedProvider,
+    old_tip_hash: B256,
+    new_tip_hash: B256,
+) -> Result<Vec<TxHash>, ProviderError> {
+    let get_block = |hash: B256| async move {
+        provider
+            .get_block_by_hash(hash)
+            .await?
+            .ok_or_else(|| ProviderError::CustomError(format!("Block {hash} not found")))
+    };
+
+    let mut old_block = get_block(old_tip_hash).await?;
+    let mut new_block = get_block(new_tip_hash).await?;
+    let mut old_fork_txs = Vec::new();
+    let mut new_fork_txs = HashSet::new();
+
+    for _ in 0..MAX_ORPHANED_TXS_WALK_DEPTH * 2 {
+        if old_block.header.hash == new_block.header.hash {
+            return Ok(old_fork_txs.into_iter().filter(|tx| !new_fork_txs.contains(tx)).collect());
+        }
+
+        if old_block.header.number >= new_block.header.number {
+            old_fork_txs.extend(old_block.transactions.hashes());
+            old_block = get_block(old_block.header.parent_hash).await?;
+        } else {
+            new_fork_txs.extend(new_block.transactions.hashes());
+            new_block = get_block(new_block.header.parent_hash).await?;
+        }
+    }
+
+    Err(ProviderError::CustomError(format!(
+        "No common ancestor found between {old_tip_hash} and {new_tip_hash} within {MAX_ORPHANED_TXS_WALK_DEPTH} blocks"
+    )))
+}
+
+/// How many blocks indexing should stay behind the chain head.
+///
+/// This is the larger of the chain's reorg safe distance (when `reorg_safe_distance` is enabled)
+/// and the network's configured `confirmation_blocks`.
+pub fn indexing_distance_from_head(
+    reorg_safe_distance: bool,
+    confirmation_blocks: Option<u64>,
+    chain_reorg_safe_distance: U64,
+) -> U64 {
+    let reorg_safe_distance =
+        if reorg_safe_distance { chain_reorg_safe_distance } else { U64::ZERO };
+
+    reorg_safe_distance.max(U64::from(confirmation_blocks.unwrap_or_default()))
+}
+
+/// The newest block which is `indexing_distance_from_head` behind `latest_block`.
+///
+/// Saturates at zero for chains shorter than the distance, such as a fresh devnet with a large
+/// `confirmation_blocks`.
+pub fn safe_block_number(latest_block: U64, indexing_distance_from_head: U64) -> U64 {
+    latest_block.saturating_sub(indexing_distance_from_head)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use alloy::{
+        consensus::Header,
+        rpc::types::{Block, BlockTransactions},
+        transports::mock::Asserter,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
     use super::*;
 
+    /// Records the target of every event emitted while it is the default subscriber.
+    struct TargetRecorder(Arc<Mutex<Vec<String>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for TargetRecorder {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            self.0.lock().unwrap().push(event.metadata().target().to_string());
+        }
+    }
+
+    #[test]
+    fn test_reorg_logs_use_reorg_target() {
+        let targets = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(TargetRecorder(targets.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            handle_chain_notification(
+                ChainStateNotification::Reverted { from_block: 120, to_block: 122 },
+                "test",
+                "reorg-target-test",
+            );
+            handle_chain_notification(
+                ChainStateNotification::Committed {
+                    from_block: 120,
+                    to_block: 122,
+                    tip_hash: B256::ZERO,
+                },
+                "test",
+                "reorg-target-test",
+            );
+        });
+
+        let targets = targets.lock().unwrap();
+        assert_eq!(targets.len(), 2);
+        assert!(targets.iter().all(|target| target == "rindexer::reorg"));
+    }
+
     #[test]
     fn test_reorg_safe_distance_for_chain() {
         let mainnet_chain_id = 1;
@@ -72,4 +671,493 @@ mod tests {
         let other_chain_id = 42;
         assert_eq!(reorg_safe_distance_for_chain(other_chain_id), U64::from(64));
     }
+
+    #[test]
+    fn test_indexing_distance_from_head() {
+        let mainnet = reorg_safe_distance_for_chain(1);
+        let polygon = reorg_safe_distance_for_chain(137);
+
+        assert_eq!(indexing_distance_from_head(false, None, mainnet), U64::ZERO);
+        assert_eq!(
+            indexing_distance_from_head(true, None, mainnet),
+            U64::from(MAINNET_REORG_SAFE_BLOCKS)
+        );
+        assert_eq!(indexing_distance_from_head(false, Some(5), mainnet), U64::from(5));
+
+        // confirmation blocks only take effect when deeper than the reorg safe distance
+        assert_eq!(
+            indexing_distance_from_head(true, Some(5), mainnet),
+            U64::from(MAINNET_REORG_SAFE_BLOCKS)
+        );
+        assert_eq!(indexing_distance_from_head(true, Some(100), mainnet), U64::from(100));
+        assert_eq!(indexing_distance_from_head(true, Some(100), polygon), U64::from(100));
+        assert_eq!(
+            indexing_distance_from_head(true, Some(10), polygon),
+            U64::from(DEFAULT_REORG_SAFE_BLOCKS)
+        );
+
+        // instant finality chains have no reorg safe distance
+        assert_eq!(indexing_distance_from_head(true, None, U64::ZERO), U64::ZERO);
+    }
+
+    #[test]
+    fn test_safe_block_number_below_confirmation_blocks() {
+        let distance =
+            indexing_distance_from_head(true, Some(32), reorg_safe_distance_for_chain(1));
+
+        assert_eq!(safe_block_number(U64::from(10), distance), U64::ZERO);
+        assert_eq!(safe_block_number(U64::from(32), distance), U64::ZERO);
+        assert_eq!(safe_block_number(U64::from(100), distance), U64::from(68));
+    }
+
+    #[test]
+    fn test_single_block_reorg_has_depth_one() {
+        // the ExEx sends the reverted range as start then end, a replaced tip has start == end
+        let reorg = handle_chain_notification(
+            ChainStateNotification::Reorged {
+                revert_from_block: 100,
+                revert_to_block: 100,
+                new_from_block: 100,
+                new_to_block: 101,
+                new_tip_hash: B256::ZERO,
+            },
+            "test",
+            "ethereum",
+        );
+
+        assert_eq!(reorg, Some(ReorgInfo { fork_block: 100, depth: 1 }));
+    }
+
+    #[test]
+    fn test_reorged_depth_and_fork_block() {
+        let reorg = handle_chain_notification(
+            ChainStateNotification::Reorged {
+                revert_from_block: 100,
+                revert_to_block: 105,
+                new_from_block: 100,
+                new_to_block: 106,
+                new_tip_hash: B256::ZERO,
+            },
+            "test",
+            "ethereum",
+        );
+
+        assert_eq!(reorg, Some(ReorgInfo { fork_block: 100, depth: 6 }));
+    }
+
+    #[test]
+    fn test_reverted_reorg_info() {
+        let reorg = handle_chain_notification(
+            ChainStateNotification::Reverted { from_block: 200, to_block: 210 },
+            "test",
+            "ethereum",
+        );
+        assert_eq!(reorg, Some(ReorgInfo { fork_block: 200, depth: 11 }));
+
+        let reorg = handle_chain_notification(
+            ChainStateNotification::Reverted { from_block: 200, to_block: 200 },
+            "test",
+            "ethereum",
+        );
+        assert_eq!(reorg, Some(ReorgInfo { fork_block: 200, depth: 1 }));
+    }
+
+    #[test]
+    fn test_exex_reorg_feeds_depth_percentiles() {
+        let network = "exex-percentile-test";
+        handle_chain_notification(
+            ChainStateNotification::Reorged {
+                revert_from_block: 100,
+                revert_to_block: 102,
+                new_from_block: 100,
+                new_to_block: 103,
+                new_tip_hash: B256::ZERO,
+            },
+            "test",
+            network,
+        );
+
+        let p99 = crate::metrics::definitions::REORG_DEPTH_PERCENTILE
+            .with_label_values(&[network, "0.99"])
+            .get();
+        assert_eq!(p99, 3.0);
+    }
+
+    #[test]
+    fn test_committed_is_not_a_reorg() {
+        let reorg = handle_chain_notification(
+            ChainStateNotification::Committed { from_block: 1, to_block: 10, tip_hash: B256::ZERO },
+            "test",
+            "ethereum",
+        );
+
+        assert_eq!(reorg, None);
+    }
+
+    #[test]
+    fn test_coalesce_notifications_keeps_deepest_reorg() {
+        let coalesced = coalesce_notifications(vec![
+            ChainStateNotification::Committed {
+                from_block: 90,
+                to_block: 99,
+                tip_hash: B256::ZERO,
+            },
+            ChainStateNotification::Reverted { from_block: 98, to_block: 99 },
+            ChainStateNotification::Reorged {
+                revert_from_block: 95,
+                revert_to_block: 99,
+                new_from_block: 95,
+                new_to_block: 100,
+                new_tip_hash: B256::from([1u8; 32]),
+            },
+            ChainStateNotification::Committed {
+                from_block: 101,
+                to_block: 101,
+                tip_hash: B256::from([2u8; 32]),
+            },
+        ]);
+
+        assert_eq!(
+            coalesced,
+            vec![
+                ChainStateNotification::Reorged {
+                    revert_from_block: 95,
+                    revert_to_block: 99,
+                    new_from_block: 95,
+                    new_to_block: 100,
+                    new_tip_hash: B256::from([1u8; 32]),
+                },
+                ChainStateNotification::Committed {
+                    from_block: 101,
+                    to_block: 101,
+                    tip_hash: B256::from([2u8; 32]),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lagged_listener_loses_dropped_notifications() {
+        let (tx, mut rx) = broadcast::channel(2);
+
+        tx.send(ChainStateNotification::Reverted { from_block: 120, to_block: 121 }).unwrap();
+        tx.send(ChainStateNotification::Reverted { from_block: 110, to_block: 121 }).unwrap();
+        tx.send(ChainStateNotification::Reverted { from_block: 115, to_block: 122 }).unwrap();
+        tx.send(ChainStateNotification::Committed {
+            from_block: 110,
+            to_block: 123,
+            tip_hash: B256::ZERO,
+        })
+        .unwrap();
+
+        // The channel only holds two notifications, so the first two are dropped and the deeper
+        // revert from block 110 is lost
+        assert!(matches!(rx.recv().await, Err(RecvError::Lagged(2))));
+        assert_eq!(
+            drain_coalesced(&mut rx),
+            vec![
+                ChainStateNotification::Reverted { from_block: 115, to_block: 122 },
+                ChainStateNotification::Committed {
+                    from_block: 110,
+                    to_block: 123,
+                    tip_hash: B256::ZERO
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_relay_coalesces_superseded_revert() {
+        let (upstream_tx, upstream_rx) = broadcast::channel(10);
+        let (downstream_tx, mut downstream_rx) = broadcast::channel(10);
+        let relay = tokio::spawn(relay_chain_notifications(
+            upstream_rx,
+            downstream_tx,
+            Duration::from_secs(5),
+        ));
+
+        upstream_tx
+            .send(ChainStateNotification::Reverted { from_block: 120, to_block: 122 })
+            .unwrap();
+        upstream_tx
+            .send(ChainStateNotification::Reorged {
+                revert_from_block: 110,
+                revert_to_block: 122,
+                new_from_block: 110,
+                new_to_block: 123,
+                new_tip_hash: B256::ZERO,
+            })
+            .unwrap();
+        drop(upstream_tx);
+        relay.await.unwrap();
+
+        assert_eq!(
+            downstream_rx.recv().await.unwrap(),
+            ChainStateNotification::Reorged {
+                revert_from_block: 110,
+                revert_to_block: 122,
+                new_from_block: 110,
+                new_to_block: 123,
+                new_tip_hash: B256::ZERO,
+            }
+        );
+        assert!(matches!(downstream_rx.recv().await, Err(RecvError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn test_lagged_relay_coalesces_backlog() {
+        let (upstream_tx, upstream_rx) = broadcast::channel(2);
+        let (downstream_tx, mut downstream_rx) = broadcast::channel(10);
+
+        upstream_tx
+            .send(ChainStateNotification::Reverted { from_block: 120, to_block: 121 })
+            .unwrap();
+        upstream_tx
+            .send(ChainStateNotification::Reverted { from_block: 118, to_block: 121 })
+            .unwrap();
+        upstream_tx
+            .send(ChainStateNotification::Reverted { from_block: 115, to_block: 122 })
+            .unwrap();
+        upstream_tx
+            .send(ChainStateNotification::Reorged {
+                revert_from_block: 116,
+                revert_to_block: 122,
+                new_from_block: 116,
+                new_to_block: 123,
+                new_tip_hash: B256::repeat_byte(1),
+            })
+            .unwrap();
+
+        // the relay starts behind, the first two notifications are dropped and the queued
+        // backlog is coalesced into a single reorg
+        tokio::spawn(relay_chain_notifications(
+            upstream_rx,
+            downstream_tx,
+            Duration::from_millis(20),
+        ));
+
+        assert_eq!(
+            downstream_rx.recv().await.unwrap(),
+            ChainStateNotification::Reorged {
+                revert_from_block: 115,
+                revert_to_block: 122,
+                new_from_block: 116,
+                new_to_block: 123,
+                new_tip_hash: B256::repeat_byte(1),
+            }
+        );
+        drop(upstream_tx);
+        assert!(matches!(downstream_rx.recv().await, Err(RecvError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn test_relay_forwards_revert_after_grace_period() {
+        let (upstream_tx, upstream_rx) = broadcast::channel(10);
+        let (downstream_tx, mut downstream_rx) = broadcast::channel(10);
+        tokio::spawn(relay_chain_notifications(
+            upstream_rx,
+            downstream_tx,
+            Duration::from_millis(20),
+        ));
+
+        upstream_tx
+            .send(ChainStateNotification::Reverted { from_block: 120, to_block: 122 })
+            .unwrap();
+
+        // the upstream sender is still open, so only the grace period can release the revert
+        assert_eq!(
+            downstream_rx.recv().await.unwrap(),
+            ChainStateNotification::Reverted { from_block: 120, to_block: 122 }
+        );
+    }
+
+    fn block(number: u64, hash: u8, parent_hash: u8, txs: &[u8]) -> Block {
+        Block {
+            header: alloy::rpc::types::Header {
+                hash: B256::repeat_byte(hash),
+                inner: Header {
+                    number,
+                    parent_hash: B256::repeat_byte(parent_hash),
+                    ..Default::default()
+                },
+                total_difficulty: None,
+                size: None,
+            },
+            uncles: vec![],
+            transactions: BlockTransactions::Hashes(
+                txs.iter().map(|tx| TxHash::repeat_byte(*tx)).collect(),
+            ),
+            withdrawals: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compute_orphaned_txs() {
+        // common ancestor 0xaa at block 10, the old fork has block 11 (0x01) and the new fork has
+        // blocks 11 (0x02) and 12 (0x03). Transaction 0x20 is re-included on the new fork.
+        let ancestor = block(10, 0xaa, 0x00, &[0x99]);
+        let asserter = Asserter::new();
+        asserter.push_success(&block(11, 0x01, 0xaa, &[0x10, 0x20]));
+        asserter.push_success(&block(12, 0x03, 0x02, &[0x40]));
+        asserter.push_success(&block(11, 0x02, 0xaa, &[0x20, 0x30]));
+        asserter.push_success(&ancestor);
+        asserter.push_success(&ancestor);
+
+        let provider = JsonRpcCachedProvider::mocked(asserter, None);
+        let orphaned =
+            compute_orphaned_txs(&provider, B256::repeat_byte(0x01), B256::repeat_byte(0x03))
+                .await
+                .unwrap();
+
+        assert_eq!(orphaned, vec![TxHash::repeat_byte(0x10)]);
+    }
+
+    /// Emits a scripted sequence of notifications.
+    struct ScriptedSource(VecDeque<ChainStateNotification>);
+
+    #[async_trait]
+    impl ReorgNotificationSource for ScriptedSource {
+        async fn next(&mut self) -> Option<ChainStateNotification> {
+            self.0.pop_front()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drive_chain_notifications_from_scripted_source() {
+        let network = "scripted-source-test";
+        let detected = || {
+            crate::metrics::definitions::REORGS_DETECTED_TOTAL.with_label_values(&[network]).get()
+        };
+
+        let source = ScriptedSource(VecDeque::from(vec![
+            ChainStateNotification::Committed {
+                from_block: 100,
+                to_block: 101,
+                tip_hash: B256::ZERO,
+            },
+            ChainStateNotification::Reverted { from_block: 100, to_block: 101 },
+            ChainStateNotification::Reorged {
+                revert_from_block: 100,
+                revert_to_block: 101,
+                new_from_block: 100,
+                new_to_block: 102,
+                new_tip_hash: B256::ZERO,
+            },
+        ]));
+
+        drive_chain_notifications(source, "test".to_string(), network.to_string()).await;
+
+        assert_eq!(detected(), 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_source_ends_when_channel_closes() {
+        let (tx, rx) = broadcast::channel(10);
+        let mut source = BroadcastNotificationSource::new(rx, "test".to_string());
+
+        tx.send(ChainStateNotification::Reverted { from_block: 120, to_block: 121 }).unwrap();
+        drop(tx);
+
+        assert_eq!(
+            source.next().await,
+            Some(ChainStateNotification::Reverted { from_block: 120, to_block: 121 })
+        );
+        assert_eq!(source.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_listener_falls_back_to_polling() {
+        let provider = Arc::new(JsonRpcCachedProvider::mocked(Asserter::new(), None));
+
+        let listener = spawn_chain_notification_listener(
+            &provider,
+            None,
+            "test".to_string(),
+            "ethereum".to_string(),
+        );
+        assert!(listener.is_none());
+
+        let listener = spawn_chain_notification_listener(
+            &provider,
+            Some(ReorgPolling::default()),
+            "test".to_string(),
+            "ethereum".to_string(),
+        )
+        .expect("should poll for reorgs without chain state notifications");
+        listener.abort();
+    }
+
+    #[tokio::test]
+    async fn test_polling_source_commits_several_blocks_per_poll() {
+        let asserter = Asserter::new();
+        asserter.push_success(&block(11, 0x0b, 0x0a, &[]));
+        // the head moves on to 13 (0x0d) through 12 (0x0c) before the next poll
+        asserter.push_success(&block(13, 0x0d, 0x0c, &[]));
+        asserter.push_success(&block(12, 0x0c, 0x0b, &[]));
+
+        let provider = Arc::new(JsonRpcCachedProvider::mocked(asserter, None));
+        let mut source = PollingNotificationSource::new(
+            provider,
+            Duration::from_millis(60),
+            64,
+            "test".to_string(),
+        );
+
+        assert_eq!(
+            source.next().await,
+            Some(ChainStateNotification::Committed {
+                from_block: 12,
+                to_block: 13,
+                tip_hash: B256::repeat_byte(0x0d)
+            })
+        );
+        assert_eq!(
+            source.recent,
+            VecDeque::from(vec![
+                (11, B256::repeat_byte(0x0b)),
+                (12, B256::repeat_byte(0x0c)),
+                (13, B256::repeat_byte(0x0d)),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_polling_source_detects_reorg() {
+        let asserter = Asserter::new();
+        // block 10 (0x0a) then 11 (0x0b) on top of it
+        asserter.push_success(&block(10, 0x0a, 0x09, &[]));
+        asserter.push_success(&block(11, 0x0b, 0x0a, &[]));
+        // block 12 (0x1c) arrives on a new branch through 11 (0x1b) forking after block 10
+        asserter.push_success(&block(12, 0x1c, 0x1b, &[]));
+        asserter.push_success(&block(11, 0x1b, 0x0a, &[]));
+
+        let provider = Arc::new(JsonRpcCachedProvider::mocked(asserter, None));
+        let mut source = PollingNotificationSource::new(
+            provider,
+            // longer than the latest block cache so every poll hits the mocked RPC
+            Duration::from_millis(60),
+            64,
+            "test".to_string(),
+        );
+
+        assert_eq!(
+            source.next().await,
+            Some(ChainStateNotification::Committed {
+                from_block: 11,
+                to_block: 11,
+                tip_hash: B256::repeat_byte(0x0b)
+            })
+        );
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(
+            source.next().await,
+            Some(ChainStateNotification::Reorged {
+                revert_from_block: 11,
+                revert_to_block: 11,
+                new_from_block: 11,
+                new_to_block: 12,
+                new_tip_hash: B256::repeat_byte(0x1c),
+            })
+        );
+    }
 }
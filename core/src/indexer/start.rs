@@ -1,4 +1,4 @@
-use std::{path::Path, sync::Arc};
+use std::{path::Path, sync::Arc, time::Duration};
 
 use alloy::primitives::U64;
 use futures::future::try_join_all;
@@ -18,6 +18,7 @@ use crate::events::RindexerEventEmitter;
 use crate::helpers::format_duration;
 use crate::indexer::native_transfer::native_transfer_block_processor;
 use crate::indexer::Indexer;
+use crate::metrics::indexing as metrics;
 use crate::{
     database::postgres::client::PostgresConnectionError,
     event::{
@@ -33,10 +34,10 @@ use crate::{
             ProcessContractsEventsWithDependenciesError, ProcessEventError,
         },
         progress::IndexingEventsProgressState,
-        reorg::reorg_safe_distance_for_chain,
+        reorg::{indexing_distance_from_head, reorg_safe_distance_for_chain, safe_block_number},
         ContractEventDependencies,
     },
-    manifest::core::Manifest,
+    manifest::{core::Manifest, network::Network},
     provider::{JsonRpcCachedProvider, ProviderError},
     PostgresClient, RindexerEvent,
 };
@@ -101,6 +102,7 @@ async fn get_start_end_block(
     event_name: &str,
     network: &str,
     reorg_safe_distance: bool,
+    network_config: Option<&Network>,
 ) -> Result<(U64, U64, U64), StartIndexingError> {
     let latest_block = provider.get_block_number().await?;
 
@@ -161,8 +163,13 @@ async fn get_start_end_block(
         }
     }
 
-    let (end_block, indexing_distance_from_head) =
-        calculate_safe_block_number(reorg_safe_distance, &provider, latest_block, end_block);
+    let (end_block, indexing_distance_from_head) = calculate_safe_block_number(
+        reorg_safe_distance,
+        network_config,
+        &provider,
+        latest_block,
+        end_block,
+    );
 
     Ok((start_block, end_block, indexing_distance_from_head))
 }
@@ -236,6 +243,7 @@ async fn start_indexing_traces(
             &format!("TraceEvents[{}]", network_name),
             &network_name,
             first_event.trace_information.reorg_safe_distance,
+            manifest.get_network(&network_name),
         )
         .await?;
 
@@ -268,6 +276,7 @@ async fn start_indexing_traces(
             start_block,
             network_details.end_block,
             indexing_distance_from_head,
+            manifest.get_network(&network_name).and_then(Network::polling_reorg_detection),
             network_name.clone(),
             cancel_token.clone(),
         ));
@@ -359,6 +368,7 @@ async fn start_indexing_contract_events(
                     &event.info_log_name(),
                     &network_contract.network,
                     event.contract.reorg_safe_distance,
+                    manifest.get_network(&network_contract.network),
                 )
                 .await;
 
@@ -426,6 +436,11 @@ async fn start_indexing_contract_events(
         //     None => {}
         // };
 
+        let network_config = manifest.get_network(&network_contract.network);
+        let chain_reorg_safe_distance =
+            chain_reorg_safe_distance(network_config, &network_contract.cached_provider);
+        let reorg_polling = network_config.and_then(Network::polling_reorg_detection);
+
         let event_processing_config: EventProcessingConfig = match event.is_factory_filter_event() {
             true => {
                 let factory_details = network_contract
@@ -463,6 +478,8 @@ async fn start_indexing_contract_events(
                     },
                     index_event_in_order: event.index_event_in_order,
                     indexing_distance_from_head,
+                    chain_reorg_safe_distance,
+                    reorg_polling,
                     cancel_token: cancel_token.clone(),
                 }
                 .into()
@@ -496,6 +513,8 @@ async fn start_indexing_contract_events(
                 },
                 index_event_in_order: event.index_event_in_order,
                 indexing_distance_from_head,
+                chain_reorg_safe_distance,
+                reorg_polling,
                 cancel_token: cancel_token.clone(),
             }
             .into(),
@@ -609,6 +628,15 @@ async fn start_indexing(
     let database = initialize_database(manifest).await?;
     let clickhouse = initialize_clickhouse(manifest).await?;
 
+    if let Some(window_secs) = manifest.global.reorg_depth_window_secs {
+        metrics::set_reorg_depth_window(Duration::from_secs(window_secs));
+    }
+    for network in &manifest.networks {
+        if let Some(min_depth) = network.min_reorg_depth_to_report {
+            metrics::set_min_reorg_depth_to_report(&network.name, min_depth);
+        }
+    }
+
     // any events which are non-blocking and can be fired in parallel
     let mut non_blocking_process_events = Vec::new();
 
@@ -725,21 +753,88 @@ pub async fn initialize_clickhouse(
     }
 }
 
+/// The network's reorg safe distance, falling back to the chain default without a network config.
+fn chain_reorg_safe_distance(
+    network_config: Option<&Network>,
+    provider: &Arc<JsonRpcCachedProvider>,
+) -> U64 {
+    network_config
+        .map(Network::reorg_safe_distance)
+        .unwrap_or_else(|| reorg_safe_distance_for_chain(provider.chain.id()))
+}
+
 pub fn calculate_safe_block_number(
     reorg_safe_distance: bool,
+    network_config: Option<&Network>,
     provider: &Arc<JsonRpcCachedProvider>,
     latest_block: U64,
     mut end_block: U64,
 ) -> (U64, U64) {
-    let mut indexing_distance_from_head = U64::ZERO;
-    if reorg_safe_distance {
-        let chain_id = provider.chain.id();
-        let reorg_safe_distance = reorg_safe_distance_for_chain(chain_id);
-        let safe_block_number = latest_block - reorg_safe_distance;
+    let chain_reorg_safe_distance = chain_reorg_safe_distance(network_config, provider);
+    let indexing_distance_from_head = indexing_distance_from_head(
+        reorg_safe_distance,
+        network_config.and_then(|network| network.confirmation_blocks),
+        chain_reorg_safe_distance,
+    );
+    if !indexing_distance_from_head.is_zero() {
+        let safe_block_number = safe_block_number(latest_block, indexing_distance_from_head);
         if end_block > safe_block_number {
             end_block = safe_block_number;
         }
-        indexing_distance_from_head = reorg_safe_distance;
     }
     (end_block, indexing_distance_from_head)
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::transports::mock::Asserter;
+
+    use super::*;
+    use crate::manifest::network::test_network;
+
+    #[test]
+    fn test_instant_finality_indexes_to_head() {
+        let provider = Arc::new(JsonRpcCachedProvider::mocked(Asserter::new(), None));
+        let instant = test_network("finality: instant");
+
+        let (end_block, distance) = calculate_safe_block_number(
+            true,
+            Some(&instant),
+            &provider,
+            U64::from(1000),
+            U64::from(1000),
+        );
+
+        assert_eq!(distance, U64::ZERO);
+        assert_eq!(end_block, U64::from(1000));
+        assert_eq!(chain_reorg_safe_distance(Some(&instant), &provider), U64::ZERO);
+    }
+
+    #[test]
+    fn test_probabilistic_finality_sets_reorg_safe_distance() {
+        let provider = Arc::new(JsonRpcCachedProvider::mocked(Asserter::new(), None));
+        let probabilistic = test_network("finality: { probabilistic: { depth: 20 } }");
+
+        let (end_block, distance) = calculate_safe_block_number(
+            true,
+            Some(&probabilistic),
+            &provider,
+            U64::from(1000),
+            U64::from(1000),
+        );
+
+        assert_eq!(distance, U64::from(20));
+        assert_eq!(end_block, U64::from(980));
+        assert_eq!(chain_reorg_safe_distance(Some(&probabilistic), &provider), U64::from(20));
+    }
+
+    #[test]
+    fn test_chain_reorg_safe_distance_without_network_config() {
+        let provider = Arc::new(JsonRpcCachedProvider::mocked(Asserter::new(), None));
+
+        assert_eq!(
+            chain_reorg_safe_distance(None, &provider),
+            U64::from(crate::indexer::MAINNET_REORG_SAFE_BLOCKS)
+        );
+    }
+}
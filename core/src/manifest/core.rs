@@ -314,6 +314,10 @@ impl Manifest {
         self.storage.csv_enabled() && contract_csv_enabled
     }
 
+    pub fn get_network(&self, network_name: &str) -> Option<&Network> {
+        self.networks.iter().find(|n| n.name == network_name)
+    }
+
     pub fn get_custom_headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
         if let Some(phantom) = &self.phantom {
@@ -16,10 +16,19 @@ pub struct Global {
 
     #[serde(default = "default_health_port")]
     pub health_port: u16,
+
+    /// Length of the rolling window reorg depth percentiles are computed over, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reorg_depth_window_secs: Option<u64>,
 }
 
 impl Default for Global {
     fn default() -> Self {
-        Self { contracts: None, etherscan_api_key: None, health_port: default_health_port() }
+        Self {
+            contracts: None,
+            etherscan_api_key: None,
+            health_port: default_health_port(),
+            reorg_depth_window_secs: None,
+        }
     }
 }
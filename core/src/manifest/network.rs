@@ -13,12 +13,13 @@ use tokio::time::sleep;
 use super::core::{deserialize_option_u64_from_string, serialize_option_u64_as_string};
 #[cfg(feature = "reth")]
 use super::reth::RethConfig;
+use crate::indexer::reorg::{reorg_safe_distance_for_chain, DEFAULT_REORG_SAFE_BLOCKS};
 
 #[cfg(feature = "reth")]
 use crate::notifications::ChainStateNotification;
 
 #[cfg(feature = "reth")]
-use crate::reth::node::start_reth_node_with_exex;
+use crate::reth::node::{start_reth_node_with_exex, RethNodeOptions};
 
 #[cfg(feature = "reth")]
 use reth::cli::Commands;
@@ -62,6 +63,36 @@ pub struct Network {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multicall3_address: Option<String>,
 
+    /// Handle chain state notifications (reorgs and reverts) for this network, defaults to true.
+    ///
+    /// When disabled, reorg notifications are not listened to at all and the user takes
+    /// responsibility for the correctness of data indexed near the head of the chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reorg_handling: Option<bool>,
+
+    /// Number of blocks to stay behind the chain head when indexing, so only blocks with at
+    /// least this many confirmations are processed. Combined with contract `reorg_safe_distance`
+    /// the larger of the two distances is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmation_blocks: Option<u64>,
+
+    /// How blocks on this network become final, defaults to the chain's usual reorg behaviour.
+    ///
+    /// Networks with `instant` finality never reorg, so reorg handling is skipped and no reorg
+    /// safe distance is applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finality: Option<NetworkFinality>,
+
+    /// Reorgs shallower than this depth are counted as shallow reorgs instead of in the main
+    /// reorg metrics, so frequent 1-block reorgs don't trigger alerts. Unset reports every reorg.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_reorg_depth_to_report: Option<u64>,
+
+    /// Detect reorgs by polling the latest block, for providers without reth chain state
+    /// notifications. Reorgs are not detected on such providers when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reorg_polling: Option<ReorgPolling>,
+
     /// Reth configuration for this network
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "reth")]
@@ -71,6 +102,76 @@ pub struct Network {
     pub reth: Option<()>,
 }
 
+impl Network {
+    /// Check if reorg handling is enabled for this network
+    pub fn is_reorg_handling_enabled(&self) -> bool {
+        self.reorg_handling.unwrap_or(true) && !self.has_instant_finality()
+    }
+
+    /// The reorg polling settings, if reorgs on this network are detected by polling
+    pub fn polling_reorg_detection(&self) -> Option<ReorgPolling> {
+        self.reorg_polling.filter(|_| self.is_reorg_handling_enabled())
+    }
+
+    /// Check if this network receives chain reorg notifications which are handled
+    pub fn has_reorg_notifications(&self) -> bool {
+        #[cfg(feature = "reth")]
+        if self.is_reth_enabled() {
+            return self.is_reorg_handling_enabled();
+        }
+
+        false
+    }
+
+    /// Check if blocks on this network are final as soon as they are produced
+    pub fn has_instant_finality(&self) -> bool {
+        matches!(self.finality, Some(NetworkFinality::Instant))
+    }
+
+    /// The number of blocks from head after which a block can no longer be reorged
+    pub fn reorg_safe_distance(&self) -> U64 {
+        match self.finality {
+            Some(NetworkFinality::Instant) => U64::ZERO,
+            Some(NetworkFinality::Probabilistic { depth }) => U64::from(depth),
+            None => reorg_safe_distance_for_chain(self.chain_id),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReorgPolling {
+    /// How often the latest block is polled, defaults to 1000ms.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll_interval_ms: Option<u64>,
+
+    /// How many recent blocks are tracked, defaults to 64. A reorg deeper than this is reported
+    /// from the oldest tracked block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+}
+
+impl ReorgPolling {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms.unwrap_or(DEFAULT_REORG_POLL_INTERVAL_MS))
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth.unwrap_or(DEFAULT_REORG_SAFE_BLOCKS as usize)
+    }
+}
+
+/// Default interval between latest block polls when detecting reorgs by polling.
+const DEFAULT_REORG_POLL_INTERVAL_MS: u64 = 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkFinality {
+    /// Blocks are final once produced, the chain never reorgs.
+    Instant,
+    /// Blocks can be reorged until they are `depth` blocks behind head.
+    Probabilistic { depth: u64 },
+}
+
 #[cfg(feature = "reth")]
 impl Network {
     /// Get the IPC path for the Reth node
@@ -102,8 +203,9 @@ impl Network {
             return Ok(None);
         }
 
-        let reth_cli = self.reth.as_ref().unwrap().to_cli().map_err(|e| eyre::eyre!(e))?;
-        let reth_tx = start_reth_node_with_exex(reth_cli)?;
+        let reth = self.reth.as_ref().unwrap();
+        let reth_cli = reth.to_cli().map_err(|e| eyre::eyre!(e))?;
+        let reth_tx = start_reth_node_with_exex(reth_cli, RethNodeOptions::from(reth))?;
 
         // Wait for IPC path to be ready if specified
         if let Some(ipc_path) = self.get_reth_ipc_path() {
@@ -258,6 +360,15 @@ pub async fn wait_for_ipc_ready(ipc_path: &str) -> Result<(), eyre::Error> {
     ))
 }
 
+/// An ethereum mainnet network with `extra_yaml` appended to its YAML, for tests.
+#[cfg(test)]
+pub(crate) fn test_network(extra_yaml: &str) -> Network {
+    serde_yaml::from_str(&format!(
+        "name: ethereum\nchain_id: 1\nrpc: https://mainnet.gateway.tenderly.co\n{extra_yaml}"
+    ))
+    .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(feature = "reth")]
@@ -284,6 +395,93 @@ mod tests {
         assert_eq!(network.max_block_range, None);
         assert_eq!(network.compute_units_per_second, None);
         assert_eq!(network.block_poll_frequency, None);
+        assert!(network.is_reorg_handling_enabled());
+        assert_eq!(network.confirmation_blocks, None);
+        assert_eq!(network.min_reorg_depth_to_report, None);
+        assert_eq!(
+            network.reorg_safe_distance(),
+            U64::from(crate::indexer::MAINNET_REORG_SAFE_BLOCKS)
+        );
+    }
+
+    #[test]
+    fn test_network_reorg_handling_disabled() {
+        let network: Network = serde_yaml::from_str(
+            r#"
+            name: ethereum
+            chain_id: 1
+            rpc: https://mainnet.gateway.tenderly.co
+            reorg_handling: false
+            "#,
+        )
+        .unwrap();
+
+        assert!(!network.is_reorg_handling_enabled());
+    }
+
+    #[test]
+    fn test_network_confirmation_blocks() {
+        let network: Network = serde_yaml::from_str(
+            r#"
+            name: ethereum
+            chain_id: 1
+            rpc: https://mainnet.gateway.tenderly.co
+            confirmation_blocks: 32
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(network.confirmation_blocks, Some(32));
+    }
+
+    #[test]
+    fn test_network_instant_finality_skips_reorg_handling() {
+        let network: Network = serde_yaml::from_str(
+            r#"
+            name: devnet
+            chain_id: 1337
+            rpc: http://localhost:8545
+            finality: instant
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(network.finality, Some(NetworkFinality::Instant));
+        assert!(network.has_instant_finality());
+        assert!(!network.is_reorg_handling_enabled());
+        assert_eq!(network.reorg_safe_distance(), U64::ZERO);
+    }
+
+    #[test]
+    fn test_network_reorg_polling() {
+        let network = test_network("reorg_polling:\n  poll_interval_ms: 2000");
+        let polling = network.polling_reorg_detection().unwrap();
+        assert_eq!(polling.poll_interval(), Duration::from_millis(2000));
+        assert_eq!(polling.depth(), DEFAULT_REORG_SAFE_BLOCKS as usize);
+
+        let network = test_network("reorg_polling: {}\nreorg_handling: false");
+        assert_eq!(network.polling_reorg_detection(), None);
+
+        assert_eq!(test_network("").polling_reorg_detection(), None);
+    }
+
+    #[test]
+    fn test_network_probabilistic_finality() {
+        let network: Network = serde_yaml::from_str(
+            r#"
+            name: ethereum
+            chain_id: 1
+            rpc: https://mainnet.gateway.tenderly.co
+            finality:
+              probabilistic:
+                depth: 20
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(network.finality, Some(NetworkFinality::Probabilistic { depth: 20 }));
+        assert!(network.is_reorg_handling_enabled());
+        assert_eq!(network.reorg_safe_distance(), U64::from(20));
     }
 
     #[test]
@@ -1,7 +1,11 @@
 #![cfg(feature = "reth")]
+use std::time::Duration;
+
 use reth::cli::Cli;
 use serde::{Deserialize, Serialize};
 
+use crate::reth::node::DEFAULT_NOTIFICATION_CHANNEL_CAPACITY;
+
 /// Default value for logging field
 fn default_true() -> bool {
     true
@@ -20,6 +24,19 @@ pub struct RethConfig {
     /// CLI args as "key value" strings (e.g., "--datadir /path/to/data")
     #[serde(default)]
     pub cli_args: Vec<String>,
+
+    /// Capacity of the chain state notification channel between the ExEx and the indexer.
+    ///
+    /// The channel never blocks the ExEx: once a listener falls this far behind, the oldest
+    /// notifications are lost and only the backlog still queued is coalesced into one reorg.
+    /// Defaults to 1000.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_channel_capacity: Option<usize>,
+
+    /// How long to hold back a `Reverted` notification in case reth supersedes it with a deeper
+    /// `Reorged`, in milliseconds. Defaults to 0, which forwards reverts immediately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reverted_grace_period_ms: Option<u64>,
 }
 
 impl RethConfig {
@@ -36,7 +53,23 @@ impl RethConfig {
         // Store args in space-separated format for clean YAML
         let cli_args = Self::combine_args_with_values(&args);
 
-        Ok(Self { enabled: true, logging: true, cli_args })
+        Ok(Self {
+            enabled: true,
+            logging: true,
+            cli_args,
+            notification_channel_capacity: None,
+            reverted_grace_period_ms: None,
+        })
+    }
+
+    /// Capacity of the chain state notification channel, falling back to the default.
+    pub fn notification_channel_capacity(&self) -> usize {
+        self.notification_channel_capacity.unwrap_or(DEFAULT_NOTIFICATION_CHANNEL_CAPACITY).max(1)
+    }
+
+    /// Grace period before trusting a `Reverted` notification, zero when not configured.
+    pub fn reverted_grace_period(&self) -> Duration {
+        Duration::from_millis(self.reverted_grace_period_ms.unwrap_or_default())
     }
 
     /// Combine CLI flags with their values into space-separated strings
@@ -103,6 +136,7 @@ impl RethConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::reth::node::RethNodeOptions;
 
     #[test]
     fn test_deserialize_basic_config() {
@@ -250,6 +284,68 @@ mod tests {
         assert!(matches!(cli.command, reth::cli::Commands::Node(_)));
     }
 
+    #[test]
+    fn test_notification_channel_capacity() {
+        let yaml = r#"
+            enabled: true
+            cli_args: []
+        "#;
+
+        let config: RethConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.notification_channel_capacity(), DEFAULT_NOTIFICATION_CHANNEL_CAPACITY);
+
+        let yaml = r#"
+            enabled: true
+            notification_channel_capacity: 64
+            cli_args: []
+        "#;
+
+        let config: RethConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.notification_channel_capacity(), 64);
+    }
+
+    #[test]
+    fn test_reverted_grace_period() {
+        let yaml = r#"
+            enabled: true
+            cli_args: []
+        "#;
+
+        let config: RethConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.reverted_grace_period().is_zero());
+
+        let yaml = r#"
+            enabled: true
+            reverted_grace_period_ms: 250
+            cli_args: []
+        "#;
+
+        let config: RethConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.reverted_grace_period(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_reth_node_options_from_config() {
+        let yaml = r#"
+            enabled: true
+            notification_channel_capacity: 64
+            reverted_grace_period_ms: 250
+            cli_args: []
+        "#;
+
+        let config: RethConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            RethNodeOptions::from(&config),
+            RethNodeOptions {
+                notification_channel_capacity: 64,
+                reverted_grace_period: Duration::from_millis(250),
+            }
+        );
+
+        let config: RethConfig = serde_yaml::from_str("enabled: true\ncli_args: []").unwrap();
+        assert_eq!(RethNodeOptions::from(&config), RethNodeOptions::default());
+    }
+
     #[test]
     fn test_logging_config() {
         // Test with logging enabled (default)
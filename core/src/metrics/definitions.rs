@@ -163,6 +163,17 @@ pub static REORGS_DETECTED_TOTAL: Lazy<CounterVec> = Lazy::new(|| {
     .expect("failed to register REORGS_DETECTED_TOTAL")
 });
 
+/// Total number of reorgs shallower than the network's `min_reorg_depth_to_report`.
+/// Labels: network
+pub static SHALLOW_REORGS_TOTAL: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "rindexer_shallow_reorgs_total",
+        "Total number of chain reorganizations below the reporting depth",
+        &["network"]
+    )
+    .expect("failed to register SHALLOW_REORGS_TOTAL")
+});
+
 /// Depth of the last detected reorg.
 /// Labels: network
 pub static REORG_DEPTH: Lazy<GaugeVec> = Lazy::new(|| {
@@ -174,6 +185,30 @@ pub static REORG_DEPTH: Lazy<GaugeVec> = Lazy::new(|| {
     .expect("failed to register REORG_DEPTH")
 });
 
+/// First block invalidated by the last detected reorg.
+/// Labels: network
+///
+/// Kept as a gauge value rather than a label so cardinality stays bounded per network.
+pub static REORG_LAST_FORK_BLOCK: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "rindexer_reorg_last_fork_block",
+        "First block invalidated by the last detected chain reorganization",
+        &["network"]
+    )
+    .expect("failed to register REORG_LAST_FORK_BLOCK")
+});
+
+/// Reorg depth percentiles over a rolling window.
+/// Labels: network, quantile (0.5/0.95/0.99)
+pub static REORG_DEPTH_PERCENTILE: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "rindexer_reorg_depth_percentile",
+        "Percentiles of chain reorganization depth over a rolling window",
+        &["network", "quantile"]
+    )
+    .expect("failed to register REORG_DEPTH_PERCENTILE")
+});
+
 // =============================================================================
 // Stream Metrics
 // =============================================================================
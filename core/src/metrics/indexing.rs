@@ -1,10 +1,39 @@
 //! Indexing-specific metrics helpers.
 
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+
 use super::definitions::{
     ACTIVE_INDEXING_TASKS, BLOCKS_BEHIND, BLOCKS_INDEXED_TOTAL, EVENTS_PROCESSED_TOTAL,
     LAST_SYNCED_BLOCK, LATEST_CHAIN_BLOCK, REORGS_DETECTED_TOTAL, REORG_DEPTH,
+    REORG_DEPTH_PERCENTILE, REORG_LAST_FORK_BLOCK, SHALLOW_REORGS_TOTAL,
 };
 
+/// Default length of the rolling window reorg depth percentiles are computed over.
+pub const DEFAULT_REORG_DEPTH_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Quantiles of reorg depth exported as gauges.
+const REORG_DEPTH_QUANTILES: [(&str, f64); 3] = [("0.5", 0.5), ("0.95", 0.95), ("0.99", 0.99)];
+
+/// Reorg depths seen per network within the rolling window.
+struct ReorgDepthWindow {
+    length: Duration,
+    depths: HashMap<String, VecDeque<(Instant, u64)>>,
+}
+
+static REORG_DEPTH_WINDOW: Lazy<Mutex<ReorgDepthWindow>> = Lazy::new(|| {
+    Mutex::new(ReorgDepthWindow { length: DEFAULT_REORG_DEPTH_WINDOW, depths: HashMap::new() })
+});
+
+/// Per-network minimum reorg depth to report, networks not set report every reorg.
+static MIN_REORG_DEPTH_TO_REPORT: Lazy<Mutex<HashMap<String, u64>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 /// Record events being indexed for a contract/event pair.
 pub fn record_events_indexed(
     network: &str,
@@ -77,7 +106,109 @@ pub fn dec_active_tasks() {
 }
 
 /// Record a chain reorganization event.
-pub fn record_reorg(network: &str, depth: u64) {
+///
+/// The fork block is exported as the value of a per-network gauge so a metrics spike can be
+/// matched against logs, without using the block number as a label.
+/// Reorgs shallower than the network's minimum reporting depth, when one is set, only increment
+/// the shallow reorg counter.
+pub fn record_reorg(network: &str, depth: u64, fork_block: u64) {
+    let min_depth = MIN_REORG_DEPTH_TO_REPORT
+        .lock()
+        .ok()
+        .and_then(|min_depths| min_depths.get(network).copied());
+    if min_depth.is_some_and(|min_depth| depth < min_depth) {
+        SHALLOW_REORGS_TOTAL.with_label_values(&[network]).inc();
+        return;
+    }
+
     REORGS_DETECTED_TOTAL.with_label_values(&[network]).inc();
     REORG_DEPTH.with_label_values(&[network]).set(depth as f64);
+    REORG_LAST_FORK_BLOCK.with_label_values(&[network]).set(fork_block as f64);
+    record_reorg_depth_percentiles(network, depth);
+}
+
+/// Set the minimum reorg depth reported in the main reorg metrics for a network.
+pub fn set_min_reorg_depth_to_report(network: &str, depth: u64) {
+    if let Ok(mut min_depths) = MIN_REORG_DEPTH_TO_REPORT.lock() {
+        min_depths.insert(network.to_string(), depth);
+    }
+}
+
+/// Set the length of the rolling window reorg depth percentiles are computed over.
+pub fn set_reorg_depth_window(length: Duration) {
+    if let Ok(mut window) = REORG_DEPTH_WINDOW.lock() {
+        window.length = length;
+    }
+}
+
+/// Add a reorg depth to the network's rolling window and refresh its percentile gauges.
+fn record_reorg_depth_percentiles(network: &str, depth: u64) {
+    let Ok(mut window) = REORG_DEPTH_WINDOW.lock() else {
+        return;
+    };
+
+    let now = Instant::now();
+    window.depths.entry(network.to_string()).or_default().push_back((now, depth));
+    refresh_network_reorg_depth_percentiles(&mut window, network, now);
+}
+
+/// Drop reorg depths which have left the rolling window and refresh the percentile gauges.
+///
+/// Called before metrics are gathered so the gauges age out even when no new reorgs arrive.
+pub fn refresh_reorg_depth_percentiles() {
+    let Ok(mut window) = REORG_DEPTH_WINDOW.lock() else {
+        return;
+    };
+
+    let now = Instant::now();
+    let networks: Vec<String> = window.depths.keys().cloned().collect();
+    for network in networks {
+        refresh_network_reorg_depth_percentiles(&mut window, &network, now);
+    }
+}
+
+/// Refresh one network's percentile gauges as of `now`, removing them once its window is empty.
+fn refresh_network_reorg_depth_percentiles(
+    window: &mut ReorgDepthWindow,
+    network: &str,
+    now: Instant,
+) {
+    let length = window.length;
+    let Some(depths) = window.depths.get_mut(network) else {
+        return;
+    };
+    while depths.front().is_some_and(|(seen, _)| now.saturating_duration_since(*seen) > length) {
+        depths.pop_front();
+    }
+
+    if depths.is_empty() {
+        window.depths.remove(network);
+        for (label, _) in REORG_DEPTH_QUANTILES {
+            let _ = REORG_DEPTH_PERCENTILE.remove_label_values(&[network, label]);
+        }
+        return;
+    }
+
+    let mut sorted: Vec<u64> = depths.iter().map(|(_, depth)| *depth).collect();
+    sorted.sort_unstable();
+
+    for (label, quantile) in REORG_DEPTH_QUANTILES {
+        REORG_DEPTH_PERCENTILE
+            .with_label_values(&[network, label])
+            .set(nearest_rank(&sorted, quantile) as f64);
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn expire_reorg_depth_percentiles(network: &str) {
+    if let Ok(mut window) = REORG_DEPTH_WINDOW.lock() {
+        let expired = Instant::now() + window.length + Duration::from_secs(1);
+        refresh_network_reorg_depth_percentiles(&mut window, network, expired);
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn nearest_rank(sorted: &[u64], quantile: f64) -> u64 {
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
 }
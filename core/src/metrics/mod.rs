@@ -49,6 +49,7 @@ use prometheus::{Encoder, TextEncoder};
 ///
 /// Returns metrics in Prometheus text exposition format.
 pub async fn metrics_handler() -> impl IntoResponse {
+    indexing::refresh_reorg_depth_percentiles();
     let encoder = TextEncoder::new();
     let metric_families = prometheus::gather();
 
@@ -68,6 +69,7 @@ pub async fn metrics_handler() -> impl IntoResponse {
 
 /// Encode all metrics to a string (for testing or custom endpoints).
 pub fn encode_metrics() -> Result<String, prometheus::Error> {
+    indexing::refresh_reorg_depth_percentiles();
     let encoder = TextEncoder::new();
     let metric_families = prometheus::gather();
     let mut buffer = Vec::new();
@@ -104,4 +106,67 @@ mod tests {
         assert!(output.contains("rindexer_last_synced_block"));
         assert!(output.contains("rindexer_blocks_behind"));
     }
+
+    #[test]
+    fn test_reorg_metrics_with_fork_block() {
+        indexing::record_reorg("ethereum", 3, 12345678);
+
+        let output = encode_metrics().expect("should encode metrics");
+        assert!(output.contains("rindexer_reorgs_detected_total"));
+        assert!(output.contains("rindexer_reorg_last_fork_block"));
+    }
+
+    #[test]
+    fn test_shallow_reorg_skips_main_counter() {
+        let network = "shallow-reorg-test";
+        indexing::set_min_reorg_depth_to_report(network, 3);
+
+        indexing::record_reorg(network, 1, 1000);
+        indexing::record_reorg(network, 3, 1000);
+
+        assert_eq!(definitions::REORGS_DETECTED_TOTAL.with_label_values(&[network]).get(), 1.0);
+        assert_eq!(definitions::SHALLOW_REORGS_TOTAL.with_label_values(&[network]).get(), 1.0);
+    }
+
+    #[test]
+    fn test_reorgs_reported_without_min_depth() {
+        let network = "unfiltered-reorg-test";
+
+        indexing::record_reorg(network, 1, 1000);
+
+        assert_eq!(definitions::REORGS_DETECTED_TOTAL.with_label_values(&[network]).get(), 1.0);
+        assert_eq!(definitions::SHALLOW_REORGS_TOTAL.with_label_values(&[network]).get(), 0.0);
+    }
+
+    #[test]
+    fn test_reorg_depth_percentiles() {
+        for depth in (1..=100).rev() {
+            indexing::record_reorg("percentile-test", depth, 1000);
+        }
+
+        let percentile = |quantile: &str| {
+            definitions::REORG_DEPTH_PERCENTILE
+                .with_label_values(&["percentile-test", quantile])
+                .get()
+        };
+        assert_eq!(percentile("0.5"), 50.0);
+        assert_eq!(percentile("0.95"), 95.0);
+        assert_eq!(percentile("0.99"), 99.0);
+    }
+
+    #[test]
+    fn test_reorg_depth_percentiles_clear_when_window_empties() {
+        let network = "percentile-expiry-test";
+        indexing::record_reorg(network, 7, 1000);
+
+        let exports_percentiles = || {
+            encode_metrics().expect("should encode metrics").lines().any(|line| {
+                line.starts_with("rindexer_reorg_depth_percentile") && line.contains(network)
+            })
+        };
+        assert!(exports_percentiles());
+
+        indexing::expire_reorg_depth_percentiles(network);
+        assert!(!exports_percentiles());
+    }
 }
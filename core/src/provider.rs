@@ -4,7 +4,7 @@ use alloy::network::{AnyNetwork, AnyRpcBlock, AnyTransactionReceipt};
 use alloy::rpc::types::{Filter, ValueOrArray};
 use alloy::{
     eips::{BlockId, BlockNumberOrTag},
-    primitives::{Address, Bytes, TxHash, U256, U64},
+    primitives::{Address, Bytes, TxHash, B256, U256, U64},
     providers::{
         ext::TraceApi,
         fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
@@ -41,12 +41,12 @@ use std::{
 use thiserror::Error;
 use tokio::sync::{broadcast::Sender, Mutex, Semaphore};
 use tokio::task::JoinError;
-use tracing::{debug, debug_span, error, Instrument};
+use tracing::{debug, debug_span, error, info, Instrument};
 use url::Url;
 
 use crate::helpers::chunk_hashset;
 use crate::layer_extensions::RpcLoggingLayer;
-use crate::manifest::network::{AddressFiltering, BlockPollFrequency};
+use crate::manifest::network::{AddressFiltering, BlockPollFrequency, Network};
 use crate::metrics::rpc as rpc_metrics;
 use crate::{event::RindexerEventFilter, manifest::core::Manifest};
 
@@ -67,6 +67,9 @@ const DEFAULT_RPC_SUPPORTED_ACCOUNT_FILTERS: usize = 1000;
 /// Maximum RPC batching size available for the provider.
 pub const RPC_CHUNK_SIZE: usize = 1000;
 
+/// Maximum number of times a block range is halved and retried in `get_logs_for_range`.
+const MAX_GET_LOGS_RANGE_RETRIES: usize = 5;
+
 /// Recommended chunk sizes for batch RPC requests.
 /// See: https://www.alchemy.com/docs/best-practices-when-using-alchemy#2-avoid-high-batch-cardinality
 pub const RECOMMENDED_RPC_CHUNK_SIZE: usize = 50;
@@ -249,6 +252,25 @@ impl JsonRpcCachedProvider {
         Ok(None)
     }
 
+    #[tracing::instrument(skip_all)]
+    pub async fn get_block_by_hash(
+        &self,
+        hash: B256,
+    ) -> Result<Option<AnyRpcBlock>, ProviderError> {
+        let start = Instant::now();
+        let result = self.provider.get_block_by_hash(hash).await;
+
+        let duration = start.elapsed().as_secs_f64();
+        rpc_metrics::record_rpc_request(
+            &self.chain.to_string(),
+            "eth_getBlockByHash",
+            result.is_ok(),
+            duration,
+        );
+
+        Ok(result?)
+    }
+
     #[tracing::instrument(skip_all)]
     pub async fn get_block_number(&self) -> Result<U64, ProviderError> {
         let number = self.provider.get_block_number().await?;
@@ -618,6 +640,65 @@ impl JsonRpcCachedProvider {
         logs
     }
 
+    /// Fetches the logs for a contract's events over an inclusive block range.
+    ///
+    /// `topics` are the event signatures (topic0) to match. The range is fetched in chunks of the
+    /// provider's `max_block_range`, if a chunk is rejected it is halved and retried. Logs are
+    /// returned in block and log index order.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_logs_for_range(
+        &self,
+        address: Address,
+        topics: &[B256],
+        from_block: U64,
+        to_block: U64,
+    ) -> Result<Vec<Log>, ProviderError> {
+        let network = self.chain.to_string();
+        let base_filter = Filter::new().address(address).event_signature(topics.to_vec());
+
+        let mut logs = Vec::new();
+        let mut chunk_from = from_block;
+        let mut chunk_size = self
+            .max_block_range
+            .unwrap_or(to_block.saturating_sub(from_block) + U64::from(1))
+            .max(U64::from(1));
+        let mut retries = 0;
+
+        while chunk_from <= to_block {
+            let chunk_to = std::cmp::min(chunk_from + chunk_size - U64::from(1), to_block);
+            let filter = base_filter
+                .clone()
+                .from_block(chunk_from.to::<u64>())
+                .to_block(chunk_to.to::<u64>());
+
+            let start = Instant::now();
+            let result = self.provider.get_logs(&filter).await;
+            let duration = start.elapsed().as_secs_f64();
+            rpc_metrics::record_rpc_request(&network, "eth_getLogs", result.is_ok(), duration);
+
+            match result {
+                Ok(chunk_logs) => {
+                    logs.extend(chunk_logs);
+                    chunk_from = chunk_to + U64::from(1);
+                    retries = 0;
+                }
+                Err(e) if retries < MAX_GET_LOGS_RANGE_RETRIES && chunk_size > U64::from(1) => {
+                    retries += 1;
+                    chunk_size = std::cmp::max(chunk_size / U64::from(2), U64::from(1));
+                    debug!(
+                        "Failed to fetch logs {} - {}, retrying with block range {}: {:?}",
+                        chunk_from, chunk_to, chunk_size, e
+                    );
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        Ok(logs)
+    }
+
     /// Get logs by chunking addresses and fetching asynchronously in batches
     #[tracing::instrument(skip_all)]
     async fn get_logs_for_address_in_batches(
@@ -684,6 +765,28 @@ impl JsonRpcCachedProvider {
             chain_state_notification: None,
         })
     }
+
+    #[cfg(test)]
+    pub fn mocked(
+        asserter: alloy::transports::mock::Asserter,
+        max_block_range: Option<U64>,
+    ) -> Self {
+        let client = RpcClient::mocked(asserter);
+        let provider =
+            ProviderBuilder::new().network::<AnyNetwork>().connect_client(client.clone());
+
+        Self {
+            provider: Arc::new(provider),
+            client,
+            cache: Mutex::new(None),
+            is_zk_chain: false,
+            chain: Chain::from(1),
+            block_poll_frequency: None,
+            address_filtering: None,
+            max_block_range,
+            chain_state_notification: None,
+        }
+    }
 }
 #[derive(Error, Debug)]
 pub enum RetryClientError {
@@ -837,6 +940,8 @@ impl CreateNetworkProvider {
                 network.rpc.clone()
             };
 
+            let reth_tx = network_chain_state_notification(network, reth_tx);
+
             // create the provider
             let provider = create_client(
                 &provider_url,
@@ -866,6 +971,22 @@ impl CreateNetworkProvider {
     }
 }
 
+/// The chain state notification sender to give a network's provider, `None` when reorg handling
+/// is disabled as nothing listens to chain state notifications.
+fn network_chain_state_notification(
+    network: &Network,
+    chain_state_notification: Option<Sender<ChainStateNotification>>,
+) -> Option<Sender<ChainStateNotification>> {
+    if network.is_reorg_handling_enabled() {
+        return chain_state_notification;
+    }
+
+    if chain_state_notification.is_some() {
+        info!("{} - Reorg handling disabled, ignoring chain state notifications", network.name);
+    }
+    None
+}
+
 /// Get a provider for a specific network
 pub fn get_network_provider<'a>(
     network: &str,
@@ -873,3 +994,79 @@ pub fn get_network_provider<'a>(
 ) -> Option<&'a CreateNetworkProvider> {
     providers.iter().find(|item| item.network_name == network)
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::transports::mock::Asserter;
+
+    use super::*;
+    use crate::manifest::network::test_network;
+
+    fn log_at(block_number: u64, log_index: u64) -> Log {
+        Log {
+            inner: alloy::primitives::Log::new_unchecked(
+                Address::ZERO,
+                vec![B256::ZERO],
+                Bytes::new(),
+            ),
+            block_number: Some(block_number),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_for_range_chunks_and_orders_logs() {
+        let asserter = Asserter::new();
+        asserter.push_success(&vec![log_at(5, 1), log_at(2, 0), log_at(5, 0)]);
+        asserter.push_success(&vec![log_at(12, 3)]);
+
+        let provider = JsonRpcCachedProvider::mocked(asserter.clone(), Some(U64::from(10)));
+        let logs = provider
+            .get_logs_for_range(Address::ZERO, &[B256::ZERO], U64::from(0), U64::from(14))
+            .await
+            .unwrap();
+
+        let positions: Vec<_> = logs.iter().map(|log| (log.block_number, log.log_index)).collect();
+        assert_eq!(
+            positions,
+            vec![(Some(2), Some(0)), (Some(5), Some(0)), (Some(5), Some(1)), (Some(12), Some(3))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_for_range_halves_rejected_range() {
+        let asserter = Asserter::new();
+        asserter.push_failure_msg("query returned more than 10000 results");
+        asserter.push_success(&vec![log_at(1, 0)]);
+        asserter.push_success(&vec![log_at(7, 0)]);
+
+        let provider = JsonRpcCachedProvider::mocked(asserter.clone(), None);
+        let logs = provider
+            .get_logs_for_range(Address::ZERO, &[B256::ZERO], U64::from(0), U64::from(9))
+            .await
+            .unwrap();
+
+        assert_eq!(logs.len(), 2);
+    }
+
+    #[test]
+    fn test_reorg_handling_disabled_drops_chain_state_notification() {
+        let (tx, _rx) = tokio::sync::broadcast::channel(10);
+
+        let disabled = test_network("reorg_handling: false");
+        assert!(network_chain_state_notification(&disabled, Some(tx.clone())).is_none());
+
+        let enabled = test_network("reorg_handling: true");
+        assert!(network_chain_state_notification(&enabled, Some(tx)).is_some());
+    }
+
+    #[test]
+    fn test_instant_finality_drops_chain_state_notification() {
+        let (tx, _rx) = tokio::sync::broadcast::channel(10);
+        let network = test_network("finality: instant");
+
+        // without a sender no reorg listener is spawned for the network
+        assert!(network_chain_state_notification(&network, Some(tx)).is_none());
+    }
+}
@@ -1,10 +1,12 @@
-use std::thread::Builder;
+use std::{thread::Builder, time::Duration};
 
 use futures::FutureExt;
 use reth::cli::Cli;
 use reth_node_ethereum::EthereumNode;
 use tokio::sync::broadcast;
 
+use crate::indexer::reorg::relay_chain_notifications;
+use crate::manifest::reth::RethConfig;
 use crate::notifications::ChainStateNotification;
 use crate::reth::exex::RindexerExEx;
 use broadcast::Sender;
@@ -15,11 +17,52 @@ const STACK_SIZE: usize = 32 * 1024 * 1024; // 32 MB
 /// The name of the execution extension.
 const EXECUTION_EXTENSION_NAME: &str = "rindexer";
 
+/// The default capacity of the chain state notification channel.
+pub const DEFAULT_NOTIFICATION_CHANNEL_CAPACITY: usize = 1000;
+
+/// Options for the chain state notifications sent by the execution extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RethNodeOptions {
+    /// Capacity of the notification channel.
+    ///
+    /// The channel is a broadcast channel so the ExEx is never blocked by a slow listener, once a
+    /// listener falls this many notifications behind the oldest ones are dropped for it.
+    pub notification_channel_capacity: usize,
+    /// How long `Reverted` notifications are held back so a superseding `Reorged` is coalesced
+    /// into a single reorg, zero forwards them immediately.
+    pub reverted_grace_period: Duration,
+}
+
+impl Default for RethNodeOptions {
+    fn default() -> Self {
+        Self {
+            notification_channel_capacity: DEFAULT_NOTIFICATION_CHANNEL_CAPACITY,
+            reverted_grace_period: Duration::ZERO,
+        }
+    }
+}
+
+impl From<&RethConfig> for RethNodeOptions {
+    fn from(config: &RethConfig) -> Self {
+        Self {
+            notification_channel_capacity: config.notification_channel_capacity(),
+            reverted_grace_period: config.reverted_grace_period(),
+        }
+    }
+}
+
 /// Starts a Reth node with the execution extension that forwards chain state notifications to the provided channel.
-pub fn start_reth_node_with_exex(cli: Cli) -> eyre::Result<Sender<ChainStateNotification>> {
+pub fn start_reth_node_with_exex(
+    cli: Cli,
+    options: RethNodeOptions,
+) -> eyre::Result<Sender<ChainStateNotification>> {
+    let RethNodeOptions { notification_channel_capacity: capacity, reverted_grace_period } =
+        options;
+
     // Create a broadcast channel for chain state notifications. Sender will go to ExEx, receiver
-    // will be returned to the caller. Buffer size of 1000 to handle bursts.
-    let (notification_tx, _notification_rx) = broadcast::channel::<ChainStateNotification>(1000);
+    // will be returned to the caller.
+    let (notification_tx, _notification_rx) =
+        broadcast::channel::<ChainStateNotification>(capacity.max(1));
 
     // Clone the sender to return it
     let notification_tx_clone = notification_tx.clone();
@@ -34,6 +77,20 @@ pub fn start_reth_node_with_exex(cli: Cli) -> eyre::Result<Sender<ChainStateNoti
                 .install_exex(EXECUTION_EXTENSION_NAME, move |ctx| {
                     tokio::task::spawn_blocking(move || {
                         tokio::runtime::Handle::current().block_on(async move {
+                            let notification_tx = if reverted_grace_period.is_zero() {
+                                notification_tx
+                            } else {
+                                // the ExEx sends to an internal channel and the relay forwards
+                                // to listeners once reverts have settled
+                                let (exex_tx, exex_rx) =
+                                    broadcast::channel::<ChainStateNotification>(capacity.max(1));
+                                tokio::spawn(relay_chain_notifications(
+                                    exex_rx,
+                                    notification_tx,
+                                    reverted_grace_period,
+                                ));
+                                exex_tx
+                            };
                             let exex = RindexerExEx::new(ctx, notification_tx);
                             eyre::Ok(exex.start())
                         })